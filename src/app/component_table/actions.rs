@@ -1,9 +1,16 @@
+use std::path::PathBuf;
+
 use text_buffer::Buffer;
 use tracing::info;
 
-use crate::dataframe::{DataType, Header};
+use crate::{
+    backend::{file_formats::file_compression::Compression, IoCommandError},
+    dataframe::{DataType, Header},
+};
 
-use super::{skim::Skim, DataTable, EditTarget};
+use super::{
+    enum_select::EnumSelect, skim::Skim, violations::ViolationList, DataTable, EditTarget,
+};
 
 impl DataTable {
     pub fn edit_column_name(&mut self) {
@@ -15,6 +22,11 @@ impl DataTable {
     }
     pub fn edit_cell(&mut self) {
         if let Some((row, col)) = self.table_state.selected_cell() {
+            if let Some(DataType::Enum(values)) = self.df.dtype_column(col) {
+                self.enum_select = EnumSelect::new(values, &self.cell_get_row_col(row, col));
+                self.edit_target = EditTarget::EnumValue((row, col));
+                return;
+            }
             self.edit_target = EditTarget::Cell((row, col));
             self.textbuffer = Buffer::from(self.cell_get_row_col(row, col));
             self.textbuffer.set_cursor(self.textbuffer.len_chars());
@@ -40,6 +52,24 @@ impl DataTable {
             }
         }
     }
+    pub fn edit_columns(&mut self) {
+        self.column_view.sync(self.df.width());
+        let pos = self
+            .table_state
+            .selected_column()
+            .and_then(|col| self.column_view.order().iter().position(|i| *i == col));
+        self.column_manager.state.select(pos.or(Some(0)));
+        self.edit_target = EditTarget::ColumnManager;
+    }
+    pub fn validate_all(&mut self) {
+        let violations = self.df.validate();
+        if violations.is_empty() {
+            self.parse_errors = vec![String::from("No violations found")];
+        } else {
+            self.violations = ViolationList::new(violations);
+            self.edit_target = EditTarget::Violations;
+        }
+    }
     pub fn apply_edit(&mut self) {
         match self.edit_target {
             EditTarget::Header(col) => self.set_column_name(col, self.textbuffer.to_string()),
@@ -60,11 +90,26 @@ impl DataTable {
                 if self.textbuffer.is_empty() {
                     self.path = None;
                 } else {
-                    self.path = Some(self.textbuffer.to_string().into());
+                    let path = PathBuf::from(self.textbuffer.to_string());
+                    // renamed to `data.csv.gz` saves gzipped, to `data.csv` plain
+                    self.compression = Compression::from_path(&path);
+                    self.path = Some(path);
                 };
             }
             EditTarget::ColumnType(_) => {
-                self.set_dtype_column(self.dtype_select.to_dtype());
+                self.set_dtype_column(self.dtype_select.to_dtype(), false);
+            }
+            EditTarget::EnumValue((row, col)) => {
+                if let Some(value) = self.enum_select.selected() {
+                    self.cell_set_row_col(row, col, value);
+                }
+            }
+            EditTarget::ColumnManager => {}
+            EditTarget::Violations => {
+                if let Some(violation) = self.violations.selected() {
+                    self.table_state
+                        .select_cell(Some((violation.row, violation.col)));
+                }
             }
             EditTarget::None => {}
         }
@@ -79,6 +124,12 @@ impl DataTable {
         } else if let EditTarget::ColumnType(_) = self.edit_target {
             self.dtype_select.state.select_next();
             // info!("{:#?}", self.dtype_select.state.selected());
+        } else if let EditTarget::EnumValue(_) = self.edit_target {
+            self.enum_select.state.select_next();
+        } else if let EditTarget::ColumnManager = self.edit_target {
+            self.column_manager.state.select_next();
+        } else if let EditTarget::Violations = self.edit_target {
+            self.violations.state.select_next();
         }
     }
     pub fn skim_select_previous(&mut self) {
@@ -87,6 +138,12 @@ impl DataTable {
         } else if let EditTarget::ColumnType(_) = self.edit_target {
             self.dtype_select.state.select_previous();
             info!("{:#?}", self.dtype_select.state.selected());
+        } else if let EditTarget::EnumValue(_) = self.edit_target {
+            self.enum_select.state.select_previous();
+        } else if let EditTarget::ColumnManager = self.edit_target {
+            self.column_manager.state.select_previous();
+        } else if let EditTarget::Violations = self.edit_target {
+            self.violations.state.select_previous();
         }
     }
     pub fn edit_cancel(&mut self) {
@@ -128,12 +185,16 @@ impl DataTable {
     }
     pub fn append_column(&mut self) {
         self.df.append_empty_column(DataType::String);
+        self.column_view.sync(self.df.width());
         self.table_state.select_column(Some(self.df.width() - 1));
     }
     pub fn move_column_right(&mut self) {
         info!("{:#?}", "Moving right");
         if let Some(col) = self.table_state.selected_column() {
             let col_right = self.df.move_column_right(col);
+            if let Some(col_right) = col_right {
+                self.column_view.swap_data(col, col_right);
+            }
             if col_right.is_some() {
                 self.table_state.select_column(col_right);
             }
@@ -142,6 +203,9 @@ impl DataTable {
     pub fn move_column_left(&mut self) {
         if let Some(col) = self.table_state.selected_column() {
             let col_left = self.df.move_column_left(col);
+            if let Some(col_left) = col_left {
+                self.column_view.swap_data(col, col_left);
+            }
             if col_left.is_some() {
                 self.table_state.select_column(col_left);
             }
@@ -173,6 +237,19 @@ impl DataTable {
             }
         }
     }
+    pub fn promote_row_to_headers(&mut self) {
+        if let Some(row) = self.table_state.selected() {
+            if self.df.is_valid_row(row) {
+                self.parse_errors = self.df.promote_row_to_headers(row);
+                self.dialect.has_headers = true;
+            }
+        }
+    }
+    pub fn demote_headers_to_row(&mut self) {
+        self.df.demote_headers_to_row();
+        self.dialect.has_headers = false;
+        self.table_state.select(Some(0));
+    }
     pub fn delete_row(&mut self) {
         if let Some(row) = self.table_state.selected() {
             self.df.remove_row(row);
@@ -181,17 +258,90 @@ impl DataTable {
     pub fn delete_column(&mut self) {
         if let Some(col) = self.table_state.selected_column() {
             self.df.remove_column(col);
+            self.column_view.remove_data(col);
         }
     }
-    pub fn set_dtype_column(&mut self, dtype: DataType) {
+    /// The type `dtype` becomes for column `col`, enums take the column's values.
+    pub fn resolve_dtype(&self, col: usize, dtype: DataType) -> DataType {
+        match dtype {
+            DataType::Enum(_) => DataType::Enum(self.df.column_distinct(col)),
+            dtype => dtype,
+        }
+    }
+    pub fn set_dtype_column(&mut self, dtype: DataType, keep_invalid: bool) {
         if let Some(col) = self.table_state.selected_column() {
             if self.df.is_valid_col(col) {
-                self.df.column_set_dtype(col, dtype);
+                let dtype = self.resolve_dtype(col, dtype);
+                match keep_invalid {
+                    true => self.df.column_set_dtype_keep_invalid(col, dtype),
+                    false => self.df.column_set_dtype(col, dtype),
+                }
             }
 
             self.set_dirty();
         }
     }
+    /// Applies a type change keeping values that do not convert, other edits apply as usual.
+    pub fn apply_edit_keep_invalid(&mut self) {
+        match self.edit_target {
+            EditTarget::ColumnType(_) => {
+                self.set_dtype_column(self.dtype_select.to_dtype(), true);
+                self.edit_target = EditTarget::None;
+                self.textbuffer = Buffer::new();
+            }
+            _ => self.apply_edit(),
+        }
+    }
+    pub fn hide_column(&mut self) {
+        self.column_view.sync(self.df.width());
+        if let Some(col) = self.table_state.selected_column() {
+            if let Some(next) = self.column_view.next(col).filter(|next| *next != col) {
+                self.column_view.set_hidden(col, true);
+                self.table_state.select_column(Some(next));
+            }
+        }
+    }
+    pub fn show_all_columns(&mut self) {
+        self.column_view.show_all();
+    }
+    pub fn toggle_column_visibility(&mut self) {
+        if let EditTarget::ColumnManager = self.edit_target {
+            if let Some(pos) = self.column_manager.state.selected() {
+                let col = self.column_view.order().get(pos).copied();
+                let last_visible = self.column_view.visible().len() == 1;
+                if col.is_some_and(|col| self.column_view.is_hidden(col) || !last_visible) {
+                    self.column_view.toggle_at(pos);
+                }
+            }
+        }
+    }
+    pub fn move_view_column_up(&mut self) {
+        if let EditTarget::ColumnManager = self.edit_target {
+            if let Some(pos) = self.column_manager.state.selected() {
+                if let Some(moved_to) = pos
+                    .checked_sub(1)
+                    .and_then(|to| self.column_view.move_to(pos, to))
+                {
+                    self.column_manager.state.select(Some(moved_to));
+                }
+            }
+        }
+    }
+    pub fn move_view_column_down(&mut self) {
+        if let EditTarget::ColumnManager = self.edit_target {
+            if let Some(pos) = self.column_manager.state.selected() {
+                if let Some(moved_to) = self.column_view.move_to(pos, pos + 1) {
+                    self.column_manager.state.select(Some(moved_to));
+                }
+            }
+        }
+    }
+    pub fn toggle_keep_layout(&mut self) {
+        self.keep_layout = !self.keep_layout;
+    }
+    pub fn toggle_keep_schema(&mut self) {
+        self.keep_schema = !self.keep_schema;
+    }
     pub fn active_header(&self) -> Option<&Header> {
         if let Some(col) = self.table_state.selected_column() {
             return self.df.header_get(col);
@@ -207,13 +357,16 @@ impl DataTable {
     }
     fn cell_set_row_col(&mut self, row: usize, col: usize, content: String) {
         if self.df.is_valid(row, col) {
-            self.df.parse_set(row, col, &content);
+            if let Err(e) = self.df.parse_set(row, col, &content) {
+                self.parse_errors = vec![format!("Rejected: {}", e)];
+                return;
+            }
         }
         self.set_dirty();
     }
     fn cell_get_row_col(&self, row: usize, col: usize) -> String {
         if self.df.is_valid(row, col) {
-            self.df.get_print(row, col)
+            self.df.get_print(row, col).into_owned()
         } else {
             // should never happen
             String::new()
@@ -230,6 +383,33 @@ impl DataTable {
         self.df.append_empty_column_named(DataType::String, name);
     }
     fn set_column_name(&mut self, col: usize, name: String) {
-        self.df.header_set(col, name);
+        let set = self.df.header_set(col, name.clone());
+        if set != name {
+            self.parse_errors = vec![format!("Column '{name}' exists, renamed to '{set}'")];
+        }
+    }
+    /// Whether an export keyed by column name may go ahead,
+    /// warns once if duplicate names would drop columns.
+    pub fn check_export(&mut self) -> bool {
+        let duplicates = self.df.duplicate_headers();
+        if duplicates.is_empty() || self.export_warned {
+            self.export_warned = false;
+            return true;
+        }
+        self.parse_errors = vec![format!(
+            "Export drops columns with duplicate names: {}. Export again to continue.",
+            duplicates.join(", ")
+        )];
+        self.export_warned = true;
+        false
+    }
+    pub fn saved(&mut self, notes: Vec<String>) {
+        self.is_dirty = false;
+        if !notes.is_empty() {
+            self.parse_errors = notes;
+        }
+    }
+    pub fn save_failed(&mut self, error: IoCommandError) {
+        self.parse_errors = vec![format!("Saving failed: {error}")];
     }
 }
@@ -12,15 +12,9 @@ impl DataTable {
         }
     }
     pub fn select_cell_right(&mut self) {
+        self.column_view.sync(self.df.width());
         if let Some((row, col)) = self.table_state.selected_cell() {
-            let col: usize = {
-                let new = col + 1;
-                if new >= self.df.width() {
-                    0
-                } else {
-                    new
-                }
-            };
+            let col: usize = self.column_view.next(col).unwrap_or(0);
             if self.df.is_valid(row, col) {
                 self.select_cell(Some((row, col)));
             }
@@ -29,14 +23,9 @@ impl DataTable {
         }
     }
     pub fn select_cell_left(&mut self) {
+        self.column_view.sync(self.df.width());
         if let Some((row, col)) = self.table_state.selected_cell() {
-            let col: usize = {
-                if col == 0 {
-                    self.df.width() - 1
-                } else {
-                    col - 1
-                }
-            };
+            let col: usize = self.column_view.previous(col).unwrap_or(0);
             if self.df.is_valid(row, col) {
                 self.select_cell(Some((row, col)));
             }
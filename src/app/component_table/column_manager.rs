@@ -0,0 +1,77 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use itertools::Itertools;
+use ratatui::{
+    layout::{Position, Rect},
+    style::{Style, Stylize},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::dataframe::Header;
+
+use super::column_view::ColumnView;
+
+#[derive(Default, Debug, Clone)]
+pub struct ColumnManager {
+    pub state: ListState,
+    area: Rect,
+}
+
+impl ColumnManager {
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, headers: &[Header], view: &ColumnView) {
+        self.area = area;
+        frame.render_widget(Clear, area);
+        let block = Block::new()
+            .borders(Borders::all())
+            .title("columns")
+            .title_bottom("toggle: space, move: alt-up/alt-down or drag")
+            .title_style(Style::default().dim());
+        let items = view
+            .order()
+            .iter()
+            .filter_map(|col| headers.get(*col).map(|h| (col, h)))
+            .map(|(col, header)| {
+                let check = match view.is_hidden(*col) {
+                    true => "[ ]",
+                    false => "[x]",
+                };
+                ListItem::new(format!("{} {}", check, header))
+            })
+            .collect_vec();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().reversed());
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+
+    /// Display position of the list entry under the mouse.
+    fn position_at(&self, column: u16, row: u16) -> Option<usize> {
+        let inner = self.area.inner(ratatui::layout::Margin::new(1, 1));
+        if inner.contains(Position::new(column, row)) {
+            Some((row - inner.y) as usize + self.state.offset())
+        } else {
+            None
+        }
+    }
+
+    /// Selects entries on click and reorders them on drag.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, view: &mut ColumnView) {
+        let Some(pos) = self.position_at(mouse.column, mouse.row) else {
+            return;
+        };
+        if pos >= view.order().len() {
+            return;
+        }
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.state.select(Some(pos)),
+            MouseEventKind::Drag(MouseButton::Left) => {
+                if let Some(selected) = self.state.selected() {
+                    if let Some(moved_to) = view.move_to(selected, pos) {
+                        self.state.select(Some(moved_to));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use itertools::Itertools;
+
+use crate::{
+    backend::file_formats::file_layout::{ColumnLayout, LayoutColumn},
+    dataframe::Header,
+};
+
+/// Display order, visibility and widths of the data columns.
+///
+/// Only affects what is rendered, the [`DataFrame`](crate::dataframe::DataFrame)
+/// keeps all columns in their original order.
+#[derive(Default, Debug, Clone)]
+pub struct ColumnView {
+    /// Data column indices in display order.
+    order: Vec<usize>,
+    /// Hidden flag per data column index.
+    hidden: Vec<bool>,
+    /// Width limit per data column index, set by shrinking or growing the column.
+    max_width: Vec<Option<u16>>,
+}
+
+impl ColumnView {
+    pub fn from_layout(layout: &ColumnLayout, headers: &[Header]) -> Self {
+        let mut view = Self {
+            order: vec![],
+            hidden: vec![false; headers.len()],
+            max_width: vec![None; headers.len()],
+        };
+        for column in layout.columns.iter() {
+            let index = (0..headers.len())
+                .find(|i| headers[*i].name() == column.name && !view.order.contains(i));
+            if let Some(index) = index {
+                view.order.push(index);
+                view.hidden[index] = !column.visible;
+                view.max_width[index] = column.max_width;
+            }
+        }
+        view.sync(headers.len());
+        view
+    }
+
+    pub fn to_layout(&self, headers: &[Header], frozen: usize) -> ColumnLayout {
+        let columns = self
+            .order
+            .iter()
+            .filter_map(|i| headers.get(*i).map(|h| (i, h)))
+            .map(|(i, h)| LayoutColumn {
+                name: h.name().to_owned(),
+                visible: !self.hidden[*i],
+                max_width: self.max_width(*i),
+            })
+            .collect_vec();
+        ColumnLayout { frozen, columns }
+    }
+
+    /// Keeps the view consistent with a frame of `width` columns.
+    pub fn sync(&mut self, width: usize) {
+        self.hidden.resize(width, false);
+        self.max_width.resize(width, None);
+        self.order.retain(|i| *i < width);
+        for col in 0..width {
+            if !self.order.contains(&col) {
+                self.order.push(col);
+            }
+        }
+    }
+
+    /// All data column indices in display order, hidden ones included.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Visible data column indices in display order.
+    pub fn visible(&self) -> Vec<usize> {
+        self.order
+            .iter()
+            .filter(|i| !self.is_hidden(**i))
+            .copied()
+            .collect_vec()
+    }
+
+    pub fn is_hidden(&self, col: usize) -> bool {
+        self.hidden.get(col).copied().unwrap_or(false)
+    }
+
+    /// The visible column displayed after `col`, wrapping around.
+    pub fn next(&self, col: usize) -> Option<usize> {
+        let visible = self.visible();
+        let pos = visible.iter().position(|i| *i == col)?;
+        visible.get((pos + 1) % visible.len()).copied()
+    }
+
+    /// The visible column displayed before `col`, wrapping around.
+    pub fn previous(&self, col: usize) -> Option<usize> {
+        let visible = self.visible();
+        let pos = visible.iter().position(|i| *i == col)?;
+        visible
+            .get((pos + visible.len() - 1) % visible.len())
+            .copied()
+    }
+
+    pub fn first(&self) -> Option<usize> {
+        self.visible().first().copied()
+    }
+
+    pub fn max_width(&self, col: usize) -> Option<u16> {
+        self.max_width.get(col).copied().flatten()
+    }
+
+    pub fn set_max_width(&mut self, col: usize, max_width: Option<u16>) {
+        if let Some(w) = self.max_width.get_mut(col) {
+            *w = max_width;
+        }
+    }
+
+    pub fn set_hidden(&mut self, col: usize, hidden: bool) {
+        if let Some(h) = self.hidden.get_mut(col) {
+            *h = hidden;
+        }
+    }
+
+    pub fn show_all(&mut self) {
+        self.hidden.iter_mut().for_each(|h| *h = false);
+    }
+
+    /// Toggles the column at display position `pos`.
+    pub fn toggle_at(&mut self, pos: usize) {
+        if let Some(col) = self.order.get(pos).copied() {
+            self.set_hidden(col, !self.is_hidden(col));
+        }
+    }
+
+    /// Moves the column at display position `from` to position `to`.
+    pub fn move_to(&mut self, from: usize, to: usize) -> Option<usize> {
+        if from < self.order.len() && to < self.order.len() {
+            let col = self.order.remove(from);
+            self.order.insert(to, col);
+            Some(to)
+        } else {
+            None
+        }
+    }
+
+    /// Follows a swap of two data columns, visibility and width move with the columns.
+    pub fn swap_data(&mut self, a: usize, b: usize) {
+        if a < self.hidden.len() && b < self.hidden.len() {
+            self.hidden.swap(a, b);
+            self.max_width.swap(a, b);
+        }
+    }
+
+    /// Follows the removal of data column `col`.
+    pub fn remove_data(&mut self, col: usize) {
+        if col < self.hidden.len() {
+            self.hidden.remove(col);
+            self.max_width.remove(col);
+        }
+        self.order.retain(|i| *i != col);
+        self.order.iter_mut().for_each(|i| {
+            if *i > col {
+                *i -= 1;
+            }
+        });
+    }
+}
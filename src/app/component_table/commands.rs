@@ -1,7 +1,9 @@
 use std::path::PathBuf;
 
 use crate::backend::{
-    file_formats::{file_csv::CsvDescription, file_multi::FileDescription},
+    file_formats::{
+        file_compression::Compression, file_csv::CsvDescription, file_multi::FileDescription,
+    },
     tasks::events::IoCommand,
 };
 
@@ -9,63 +11,60 @@ use super::DataTable;
 
 impl DataTable {
     pub fn save_csv_command(&self) -> IoCommand {
-        IoCommand::SaveCsv(CsvDescription {
+        IoCommand::SaveCsv(self.csv_description())
+    }
+
+    /// The table with what is needed to write it as csv.
+    pub fn csv_description(&self) -> CsvDescription {
+        CsvDescription {
             df: self.df.clone(),
-            delim: self.delim,
+            dialect: self.dialect,
+            encoding: self.encoding,
+            compression: self.compression,
             errors: vec![],
             path: self.path.clone(),
-        })
+            keep_schema: self.keep_schema,
+            layout: self.keep_layout.then(|| {
+                self.column_view
+                    .to_layout(self.df.headers(), self.frozen_columns)
+            }),
+        }
     }
 
     pub fn save_json_command(&self) -> IoCommand {
         let rows = self.df.mapped_rows_owned();
-        let path = match self.path {
-            Some(ref path) => {
-                let mut path = path.clone();
-                path.set_extension("json");
-                path
-            }
-            None => PathBuf::from("export.json"),
-        };
+        let path = self.export_path("json", self.compression);
         IoCommand::SaveJson(FileDescription { path, rows })
     }
 
     pub fn save_yml_command(&self) -> IoCommand {
         let rows = self.df.mapped_rows_owned();
-        let path = match self.path {
-            Some(ref path) => {
-                let mut path = path.clone();
-                path.set_extension("yml");
-                path
-            }
-            None => PathBuf::from("export.yml"),
-        };
+        let path = self.export_path("yml", self.compression);
         IoCommand::SaveYml(FileDescription { path, rows })
     }
 
     pub fn save_ron_command(&self) -> IoCommand {
         let rows = self.df.mapped_rows_owned();
-        let path = match self.path {
-            Some(ref path) => {
-                let mut path = path.clone();
-                path.set_extension("ron");
-                path
-            }
-            None => PathBuf::from("export.ron"),
-        };
+        let path = self.export_path("ron", self.compression);
         IoCommand::SaveRon(FileDescription { path, rows })
     }
 
     pub fn save_toml_command(&self) -> IoCommand {
         let rows = self.df.mapped_rows_owned();
-        let path = match self.path {
-            Some(ref path) => {
-                let mut path = path.clone();
-                path.set_extension("toml");
-                path
-            }
-            None => PathBuf::from("export.toml"),
-        };
+        let path = self.export_path("toml", self.compression);
         IoCommand::SaveToml(FileDescription { rows, path })
     }
+
+    pub fn save_json_schema_command(&self) -> IoCommand {
+        let schema = self.df.json_schema();
+        let path = self.export_path("schema.json", Compression::None);
+        IoCommand::SaveJsonSchema { path, schema }
+    }
+
+    /// The path of the table with `extension`, `export.<extension>` without one,
+    /// compressed files keep the compression extension last: `data.json.gz`.
+    fn export_path(&self, extension: &str, compression: Compression) -> PathBuf {
+        let path = self.path.clone().unwrap_or_else(|| PathBuf::from("export"));
+        compression.with_extension(&path, extension)
+    }
 }
@@ -1,31 +1,53 @@
 use ratatui::{
     layout::Rect,
     style::{Style, Stylize},
+    text::Line,
     widgets::{Block, Borders, Clear, List, ListState},
     Frame,
 };
 
-use crate::dataframe::DataType;
+use crate::dataframe::{ConversionPreview, DataType};
 
 #[derive(Default, Debug, Clone)]
 pub struct DTypeSelect {
     pub state: ListState,
+    /// Key applying the change but keeping values that do not convert.
+    pub keep_invalid_key: Option<String>,
 }
 
 impl DTypeSelect {
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, preview: Option<&ConversionPreview>) {
         frame.render_widget(Clear, area);
-        let block = Block::new()
+        let mut block = Block::new()
             .borders(Borders::all())
             .title("change column datatype")
             .title_style(Style::default().dim());
+        if let Some(preview) = preview.filter(|p| p.lost > 0) {
+            let keep = match &self.keep_invalid_key {
+                Some(key) => format!(" - {key}: keep as invalid"),
+                None => String::new(),
+            };
+            block = block.title_bottom(
+                Line::from(format!(
+                    "{} lost: {}{keep}",
+                    preview.lost,
+                    preview.examples.join(", ")
+                ))
+                .red(),
+            );
+        }
         let items = vec![
             // DataType::Null,
             DataType::Bool,
             DataType::Int,
             DataType::Float,
+            DataType::Decimal,
             DataType::Date,
+            DataType::DateTime,
+            DataType::Time,
+            DataType::Duration,
             DataType::String,
+            DataType::Enum(vec![]),
         ];
         let list = List::new(items)
             .block(block)
@@ -40,7 +62,12 @@ impl DTypeSelect {
                 0 => DataType::Bool,
                 1 => DataType::Int,
                 2 => DataType::Float,
-                3 => DataType::Date,
+                3 => DataType::Decimal,
+                4 => DataType::Date,
+                5 => DataType::DateTime,
+                6 => DataType::Time,
+                7 => DataType::Duration,
+                9 => DataType::Enum(vec![]),
                 _ => DataType::String,
             }
         } else {
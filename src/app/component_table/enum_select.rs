@@ -0,0 +1,47 @@
+use itertools::Itertools;
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    widgets::{Block, Borders, Clear, List, ListState},
+    Frame,
+};
+
+/// Picker for the values of an [`Enum`](crate::dataframe::DataType::Enum) column.
+#[derive(Default, Debug, Clone)]
+pub struct EnumSelect {
+    pub state: ListState,
+    values: Vec<String>,
+}
+
+impl EnumSelect {
+    pub fn new(values: Vec<String>, current: &str) -> Self {
+        let selected = values.iter().position(|v| v == current).unwrap_or(0);
+        Self {
+            state: ListState::default().with_selected(Some(selected)),
+            values,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn selected(&self) -> Option<String> {
+        self.state
+            .selected()
+            .and_then(|i| self.values.get(i))
+            .cloned()
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+        let block = Block::new()
+            .borders(Borders::all())
+            .title("select value")
+            .title_style(Style::default().dim());
+        let list = List::new(self.values.iter().map(String::as_str).collect_vec())
+            .block(block)
+            .highlight_style(Style::default().reversed());
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
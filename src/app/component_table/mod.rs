@@ -3,22 +3,34 @@ use std::path::PathBuf;
 
 mod actions;
 mod actions_cell_select;
+mod column_manager;
+mod column_view;
 mod commands;
 mod dtype_select;
+mod enum_select;
 mod extensions;
 mod popups;
 mod render;
 mod skim;
+mod text;
+mod viewport;
+mod violations;
 
+use column_manager::ColumnManager;
+use column_view::ColumnView;
 use dtype_select::DTypeSelect;
+use enum_select::EnumSelect;
 use skim::Skim;
 use text_buffer::Buffer;
 #[allow(unused)]
 use tracing::info;
+use violations::ViolationList;
 
 use crate::{
-    backend::file_formats::file_csv::CsvDescription,
-    dataframe::{DataFrame, DataType},
+    backend::file_formats::{
+        file_compression::Compression, file_csv::CsvDescription, file_encoding::FileEncoding,
+    },
+    dataframe::{csv::CsvDialect, DataFrame, DataType},
 };
 
 #[derive(Default, Debug, Clone)]
@@ -26,9 +38,12 @@ pub enum EditTarget {
     #[default]
     None,
     Cell((usize, usize)),
+    EnumValue((usize, usize)),
     Header(usize),
     FileName,
     ColumnType(DataType),
+    ColumnManager,
+    Violations,
 }
 
 #[derive(Debug)]
@@ -38,11 +53,25 @@ pub struct DataTable {
     pub textbuffer: text_buffer::Buffer,
     pub edit_target: EditTarget,
     pub dtype_select: DTypeSelect,
+    pub enum_select: EnumSelect,
+    pub column_manager: ColumnManager,
+    pub column_view: ColumnView,
+    pub violations: ViolationList,
+    pub keep_layout: bool,
+    pub keep_schema: bool,
     pub skim: Option<Skim>,
+    pub column_offset: usize,
+    pub frozen_columns: usize,
+    pub max_column_width: u16,
+    pub wrap_cells: bool,
     pub path: Option<PathBuf>,
-    pub delim: char,
+    pub dialect: CsvDialect,
+    pub encoding: FileEncoding,
+    pub compression: Compression,
     pub is_dirty: bool,
     pub parse_errors: Vec<String>,
+    /// Set after warning that an export would drop duplicate columns.
+    pub export_warned: bool,
 }
 
 impl Default for DataTable {
@@ -53,11 +82,24 @@ impl Default for DataTable {
             textbuffer: Buffer::new(),
             edit_target: EditTarget::None,
             dtype_select: DTypeSelect::default(),
+            enum_select: EnumSelect::default(),
+            column_manager: ColumnManager::default(),
+            column_view: ColumnView::default(),
+            violations: ViolationList::default(),
+            keep_layout: false,
+            keep_schema: false,
             skim: None,
+            column_offset: 0,
+            frozen_columns: 0,
+            max_column_width: 40,
+            wrap_cells: false,
             path: Some(PathBuf::from("new.csv")),
-            delim: ';',
+            dialect: CsvDialect::default(),
+            encoding: FileEncoding::default(),
+            compression: Compression::None,
             is_dirty: false,
             parse_errors: vec![],
+            export_warned: false,
         }
     }
 }
@@ -71,7 +113,16 @@ impl From<CsvDescription> for DataTable {
         data_table.parse_errors = csv_description.errors;
 
         data_table.path = csv_description.path;
-        data_table.delim = csv_description.delim;
+        data_table.dialect = csv_description.dialect;
+        data_table.encoding = csv_description.encoding;
+        data_table.compression = csv_description.compression;
+        data_table.keep_schema = csv_description.keep_schema;
+
+        if let Some(layout) = csv_description.layout {
+            data_table.column_view = ColumnView::from_layout(&layout, data_table.df.headers());
+            data_table.frozen_columns = layout.frozen;
+            data_table.keep_layout = true;
+        }
         data_table
     }
 }
@@ -11,7 +11,10 @@ use super::{extensions::BufferExt, DataTable};
 
 impl From<DataType> for ListItem<'static> {
     fn from(data_type: DataType) -> Self {
-        ListItem::new(format!("{:?}", data_type))
+        match data_type {
+            DataType::Enum(_) => ListItem::new("Enum"),
+            _ => ListItem::new(format!("{:?}", data_type)),
+        }
     }
 }
 
@@ -21,9 +24,48 @@ impl DataTable {
             x: area.width / 3,
             y: area.height / 4,
             width: area.width / 4,
-            height: 7,
+            height: 12,
+        };
+        let preview = self.table_state.selected_column().map(|col| {
+            let dtype = self.resolve_dtype(col, self.dtype_select.to_dtype());
+            self.df.column_preview_dtype(col, &dtype)
+        });
+        self.dtype_select
+            .render(frame, popup_area, preview.as_ref());
+    }
+
+    pub fn render_popup_enum_select(&mut self, frame: &mut Frame, area: Rect) {
+        let y = area.height / 4;
+        let popup_area = Rect {
+            x: area.width / 3,
+            y,
+            width: area.width / 4,
+            height: (self.enum_select.len() as u16 + 2).min(area.height.saturating_sub(y)),
+        };
+        self.enum_select.render(frame, popup_area);
+    }
+
+    pub fn render_popup_column_manager(&mut self, frame: &mut Frame, area: Rect) {
+        let y = area.height / 6;
+        let popup_area = Rect {
+            x: area.width / 3,
+            y,
+            width: area.width / 3,
+            height: (self.df.width() as u16 + 2).min(area.height.saturating_sub(y)),
+        };
+        self.column_manager
+            .render(frame, popup_area, self.df.headers(), &self.column_view);
+    }
+
+    pub fn render_popup_violations(&mut self, frame: &mut Frame, area: Rect) {
+        let y = area.height / 6;
+        let popup_area = Rect {
+            x: area.width / 4,
+            y,
+            width: area.width / 2,
+            height: (self.violations.len() as u16 + 2).min(area.height.saturating_sub(y)),
         };
-        self.dtype_select.render(frame, popup_area);
+        self.violations.render(frame, popup_area, self.df.headers());
     }
 
     pub fn render_popup_edit_cell(&mut self, frame: &mut Frame, area: Rect) {
@@ -1,3 +1,4 @@
+use encoding_rs::UTF_8;
 use itertools::Itertools;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -7,7 +8,11 @@ use ratatui::{
     Frame,
 };
 
-use super::{extensions::BufferExt, DataTable, EditTarget};
+use super::{
+    extensions::BufferExt,
+    text::{truncate, wrap},
+    DataTable, EditTarget,
+};
 
 impl DataTable {
     pub fn render(&mut self, frame: &mut Frame, area: Rect) {
@@ -20,14 +25,24 @@ impl DataTable {
         )
         .areas(area);
 
-        let table = self.rat_table();
+        let columns = self.visible_columns(top.width.saturating_sub(2));
+        let table = self.rat_table(&columns);
+
+        // ratatui indexes the selected column into the rendered cells
+        let selected_column = self.table_state.selected_column();
+        self.table_state
+            .select_column(selected_column.and_then(|c| columns.iter().position(|v| *v == c)));
         frame.render_stateful_widget(table, top, &mut self.table_state);
+        self.table_state.select_column(selected_column);
 
         match self.edit_target {
             EditTarget::Cell((_, _)) => self.render_popup_edit_cell(frame, area),
+            EditTarget::EnumValue((_, _)) => self.render_popup_enum_select(frame, area),
             EditTarget::FileName => self.render_popup_edit(frame, area),
             EditTarget::Header(_) => self.render_popup_edit(frame, area),
             EditTarget::ColumnType(_) => self.render_popup_dtype_select(frame, area),
+            EditTarget::ColumnManager => self.render_popup_column_manager(frame, area),
+            EditTarget::Violations => self.render_popup_violations(frame, area),
             EditTarget::None => {}
         }
 
@@ -40,42 +55,64 @@ impl DataTable {
 }
 
 impl DataTable {
-    pub fn rat_row_header(&self) -> widgets::Row<'static> {
-        let cells = self
-            .df
-            .headers()
+    pub fn rat_row_header(&self, columns: &[usize]) -> widgets::Row<'static> {
+        let headers = self.df.headers();
+        let widths = self.column_widths();
+        let cells = columns
             .iter()
-            .map(|s| widgets::Cell::new(Text::raw(s.name().to_owned())))
+            .map(|col| widgets::Cell::new(Text::raw(truncate(headers[*col].name(), widths[*col]))))
             .collect_vec();
         widgets::Row::new(cells).bold()
     }
-    pub fn rat_row_footer(&self) -> widgets::Row<'static> {
-        let cells = self
-            .df
-            .headers()
+    pub fn rat_row_footer(&self, columns: &[usize]) -> widgets::Row<'static> {
+        let headers = self.df.headers();
+        let cells = columns
             .iter()
-            .map(|s| widgets::Cell::new(Text::raw(s.dtype().to_string())))
+            .map(|col| widgets::Cell::new(Text::raw(headers[*col].dtype().to_string())))
             .collect_vec();
         widgets::Row::new(cells).bold()
     }
-    pub fn rat_rows(&self) -> Vec<widgets::Row<'static>> {
+    pub fn rat_rows(&self, columns: &[usize]) -> Vec<widgets::Row<'static>> {
+        let widths = self.column_widths();
+        let headers = self.df.headers();
         let mut rows = vec![];
-        for (i, r) in self.df.rows().iter().enumerate() {
-            let cells = r
+        for i in 0..self.df.height() {
+            let mut height = 1;
+            let cells = columns
                 .iter()
-                .map(|s| widgets::Cell::new(s.print()))
+                .filter_map(|col| {
+                    let column = &self.df.columns()[*col];
+                    column
+                        .print(i, &headers[*col])
+                        .map(|s| (column.is_invalid(i), s, widths[*col]))
+                })
+                .map(|(invalid, s, width)| {
+                    let cell = match self.wrap_cells {
+                        true => {
+                            let lines = wrap(&s, width);
+                            height = height.max(lines.len() as u16);
+                            widgets::Cell::new(Text::from_iter(lines))
+                        }
+                        false => widgets::Cell::new(truncate(&s, width)),
+                    };
+                    match invalid {
+                        true => cell.red(),
+                        false => cell,
+                    }
+                })
                 .collect_vec();
             // let row = widgets::Row::new(cells);
+            let cells = widgets::Row::new(cells).height(height);
             let row = if i % 2 == 1 {
-                widgets::Row::new(cells).style(Style::default().dim())
+                cells.style(Style::default().dim())
             } else {
-                widgets::Row::new(cells)
+                cells
             };
             rows.push(row);
         }
         rows
     }
-    pub fn rat_table(&self) -> widgets::Table<'static> {
+    pub fn rat_table(&self, columns: &[usize]) -> widgets::Table<'static> {
         let path = match self.is_dirty {
             false => self.path.to_cursor_string().to_string(),
             true => {
@@ -101,7 +138,26 @@ impl DataTable {
             Some((row, col)) => format!("Row: {}, Col: {}", row, col),
             None => String::new(),
         };
-        let title = format!("{path:} - Cell <{}> - Column type <{}>", pos, dtypecol);
+        let frozen = match self.frozen_columns {
+            0 => String::new(),
+            n => format!(" - Frozen <{}>", n),
+        };
+        let hidden = match self
+            .df
+            .width()
+            .saturating_sub(self.column_view.visible().len())
+        {
+            0 => String::new(),
+            n => format!(" - Hidden <{}>", n),
+        };
+        let encoding = match self.encoding.encoding == UTF_8 {
+            true => String::new(),
+            false => format!(" - {}", self.encoding.name()),
+        };
+        let title = format!(
+            "{path:}{encoding} - Dialect <{}> - Cell <{}> - Column type <{}>{}{}",
+            self.dialect, pos, dtypecol, frozen, hidden
+        );
         let bottom_title = match self.edit_target {
             EditTarget::None => String::from(
                 "help: ?, new column: c, rename column: v, new row: r, rename file: f, save: ctrl-s, quit: q or ctrl-c",
@@ -116,17 +172,20 @@ impl DataTable {
             .title(title)
             .title_style(Style::default().light_green());
 
-        let table = Table::new(self.rat_rows(), self.min_column_widths())
-            .header(self.rat_row_header())
-            .footer(self.rat_row_footer())
+        let table = Table::new(self.rat_rows(columns), self.min_column_widths(columns))
+            .header(self.rat_row_header(columns))
+            .footer(self.rat_row_footer(columns))
             .row_highlight_style(Style::default().cyan().bold())
             // .column_highlight_style(Style::new())
             .cell_highlight_style(Style::new().bold().reversed());
         table.block(block)
     }
-    fn min_column_widths(&self) -> Vec<Constraint> {
-        let widths = self.df.min_column_widths();
-        widths.into_iter().map(Constraint::Length).collect_vec()
+    fn min_column_widths(&self, columns: &[usize]) -> Vec<Constraint> {
+        let widths = self.column_widths();
+        columns
+            .iter()
+            .map(|col| Constraint::Length(widths[*col]))
+            .collect_vec()
     }
     #[allow(unused)]
     fn equal_column_widths(&self) -> Vec<Constraint> {
@@ -0,0 +1,50 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const ELLIPSIS: char = '…';
+
+/// Cuts `text` to `width` display columns, marking the cut with an ellipsis.
+pub fn truncate(text: &str, width: u16) -> String {
+    let width = width as usize;
+    if text.width() <= width {
+        return text.to_owned();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut res = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > width - 1 {
+            break;
+        }
+        res.push(c);
+        used += w;
+    }
+    res.push(ELLIPSIS);
+    res
+}
+
+/// Splits `text` into lines of at most `width` display columns.
+pub fn wrap(text: &str, width: u16) -> Vec<String> {
+    let width = (width as usize).max(1);
+    let mut lines = vec![];
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut used = 0;
+        for c in paragraph.chars() {
+            let w = c.width().unwrap_or(0);
+            if used + w > width && !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                used = 0;
+            }
+            line.push(c);
+            used += w;
+        }
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
@@ -0,0 +1,133 @@
+use itertools::Itertools;
+#[allow(unused)]
+use tracing::info;
+
+use super::DataTable;
+
+/// Spacing ratatui puts between two table columns.
+const COLUMN_SPACING: u16 = 1;
+/// Narrowest a column can be shrunk to, one character plus the ellipsis.
+const MIN_COLUMN_WIDTH: u16 = 2;
+
+impl DataTable {
+    /// Columns that fit into `width` in display order, frozen columns first.
+    ///
+    /// Scrolls the column offset so the selected column stays visible.
+    pub fn visible_columns(&mut self, width: u16) -> Vec<usize> {
+        self.column_view.sync(self.df.width());
+        let order = self.column_view.visible();
+        let all_widths = self.column_widths();
+        let widths = order.iter().map(|col| all_widths[*col]).collect_vec();
+        let frozen = self.frozen_columns.min(widths.len());
+
+        let mut used: u16 = 0;
+        let mut positions = vec![];
+        for (pos, w) in widths.iter().enumerate().take(frozen) {
+            positions.push(pos);
+            used = used.saturating_add(*w).saturating_add(COLUMN_SPACING);
+        }
+        let available = width.saturating_sub(used);
+
+        if let Some(selected) = self.table_state.selected_column() {
+            if self.column_view.is_hidden(selected) {
+                self.table_state.select_column(order.first().copied());
+            }
+        }
+
+        self.column_offset = self.column_offset.max(frozen);
+        let selected = self
+            .table_state
+            .selected_column()
+            .and_then(|col| order.iter().position(|c| *c == col));
+        if let Some(selected) = selected {
+            if selected >= frozen {
+                if selected < self.column_offset {
+                    self.column_offset = selected;
+                }
+                while self.column_offset < selected
+                    && span_width(&widths[self.column_offset..=selected]) > available
+                {
+                    self.column_offset += 1;
+                }
+            }
+        }
+        self.column_offset = self.column_offset.min(widths.len().saturating_sub(1));
+
+        for (pos, w) in widths
+            .iter()
+            .enumerate()
+            .skip(self.column_offset.max(frozen))
+        {
+            let needed = used.saturating_add(*w);
+            if needed > width && positions.len() > frozen {
+                break;
+            }
+            positions.push(pos);
+            used = needed.saturating_add(COLUMN_SPACING);
+        }
+        positions.into_iter().map(|pos| order[pos]).collect_vec()
+    }
+
+    /// Column widths as rendered, capped by the width the column was sized to
+    /// or else by the default maximum width.
+    pub fn column_widths(&self) -> Vec<u16> {
+        self.df
+            .min_column_widths()
+            .into_iter()
+            .enumerate()
+            .map(|(col, width)| match self.column_view.max_width(col) {
+                Some(max) => width.min(max),
+                None => width.min(self.max_column_width),
+            })
+            .collect_vec()
+    }
+
+    pub fn shrink_column(&mut self) {
+        if let Some(col) = self.table_state.selected_column() {
+            if let Some(width) = self.column_widths().get(col) {
+                let width = width.saturating_sub(1).max(MIN_COLUMN_WIDTH);
+                self.column_view.sync(self.df.width());
+                self.column_view.set_max_width(col, Some(width));
+            }
+        }
+    }
+
+    pub fn grow_column(&mut self) {
+        if let Some(col) = self.table_state.selected_column() {
+            if let Some(width) = self.column_widths().get(col) {
+                let natural = self.df.min_column_widths()[col];
+                let width = width.saturating_add(1).min(natural);
+                self.column_view.sync(self.df.width());
+                self.column_view.set_max_width(col, Some(width));
+            }
+        }
+    }
+
+    pub fn toggle_wrap_cells(&mut self) {
+        self.wrap_cells = !self.wrap_cells;
+    }
+
+    pub fn toggle_freeze_columns(&mut self) {
+        let visible = self.column_view.visible();
+        let selected = self
+            .table_state
+            .selected_column()
+            .and_then(|col| visible.iter().position(|c| *c == col));
+        if let Some(pos) = selected {
+            if self.frozen_columns == pos + 1 {
+                self.frozen_columns = 0;
+            } else {
+                self.frozen_columns = pos + 1;
+            }
+        }
+    }
+}
+
+fn span_width(widths: &[u16]) -> u16 {
+    widths
+        .iter()
+        .fold(0u16, |acc, w| {
+            acc.saturating_add(*w).saturating_add(COLUMN_SPACING)
+        })
+        .saturating_sub(COLUMN_SPACING)
+}
@@ -0,0 +1,54 @@
+use itertools::Itertools;
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::dataframe::{Header, Violation};
+
+/// Result of validating the whole table, lets the user jump to each cell.
+#[derive(Default, Debug, Clone)]
+pub struct ViolationList {
+    pub state: ListState,
+    violations: Vec<Violation>,
+}
+
+impl ViolationList {
+    pub fn new(violations: Vec<Violation>) -> Self {
+        Self {
+            state: ListState::default().with_selected(Some(0)),
+            violations,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.violations.len()
+    }
+
+    pub fn selected(&self) -> Option<&Violation> {
+        self.state.selected().and_then(|i| self.violations.get(i))
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, headers: &[Header]) {
+        frame.render_widget(Clear, area);
+        let block = Block::new()
+            .borders(Borders::all())
+            .title(format!("{} violations", self.violations.len()))
+            .title_bottom("jump to cell: enter")
+            .title_style(Style::default().dim());
+        let items = self
+            .violations
+            .iter()
+            .map(|v| {
+                let name = headers.get(v.col).map(|h| h.name()).unwrap_or_default();
+                ListItem::new(format!("Row {} <{}>: {}", v.row, name, v.violation))
+            })
+            .collect_vec();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().reversed());
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
@@ -1,13 +1,16 @@
 use crokey::Combiner;
-use ratatui::{text::Line, Frame};
+use ratatui::{layout::Rect, style::Stylize, text::Line, Frame};
 use tokio::sync::mpsc::UnboundedSender;
 use tracing::info;
 
 use crate::{
     backend::{
-        file_formats::file_csv::CsvDescription, key_bindings::KeyBindings,
-        tasks::events::IoCommand, IoCommandError, IoCommandResult,
+        file_formats::file_csv::CsvDescription,
+        key_bindings::KeyBindings,
+        tasks::events::{IoCommand, LoadProgress},
+        IoCommandError, IoCommandResult,
     },
+    dataframe::{DataFrame, FrameError, FrameResult},
     utils::cli::Cli,
 };
 
@@ -26,6 +29,11 @@ pub struct App {
     pub running: bool,
     pub data: DataTable,
     pub io_error: Option<IoCommandError>,
+    /// Set while a csv file is loading, the table only shows a preview then.
+    pub loading: Option<LoadProgress>,
+    /// Set when loading was cancelled, the table then only holds the preview
+    /// and is read-only so that saving can't cut the file short.
+    pub cancelled: bool,
     pub show_key_bindings: bool,
     pub key_bindings_display: KeyBindingsDisplay,
 }
@@ -48,6 +56,21 @@ impl App {
         } else {
             self.data.render(frame, area);
         }
+
+        let status = match &self.loading {
+            Some(progress) => format!(" {progress} - esc to cancel "),
+            None if self.cancelled => format!(
+                " loading cancelled, showing the first {} rows read only ",
+                self.data.df.height()
+            ),
+            None => return,
+        };
+        let status_area = Rect {
+            y: area.bottom().saturating_sub(1),
+            height: 1.min(area.height),
+            ..area
+        };
+        frame.render_widget(Line::from(status).reversed(), status_area);
     }
 }
 
@@ -62,24 +85,51 @@ impl App {
             running: true,
             data: DataTable::default(),
             io_error: None,
+            loading: None,
+            cancelled: false,
             show_key_bindings: false,
             key_bindings_display: KeyBindingsDisplay::default(),
         }
     }
 
+    pub fn load_progress(&mut self, progress: LoadProgress) {
+        self.loading = Some(progress);
+    }
+
+    /// Shows the rows of a csv that is still loading.
+    pub fn load_preview(&mut self, df: DataFrame) {
+        self.data = DataTable {
+            df,
+            path: self.cli.file_path(),
+            ..DataTable::default()
+        };
+    }
+
+    pub fn cancel_load(&mut self) {
+        if self.loading.is_some() {
+            self.io_command_sender
+                .send(IoCommand::CancelLoad)
+                .expect("IoCommand Receiver Closed. Quitting");
+        }
+    }
+
     pub fn from_parsed_csv(&mut self, data: IoCommandResult<CsvDescription>) {
+        self.loading = None;
+        self.cancelled = false;
         match data {
             Ok(csv_description) => {
                 self.io_error = None;
                 self.data = csv_description.into();
             }
+            // keep the preview, the rows past it were never read
+            Err(IoCommandError::DataFrame(FrameError::Cancelled)) => {
+                self.io_error = None;
+                self.cancelled = true;
+            }
             Err(e) => {
                 self.io_error = Some(e);
                 self.data = DataTable::default();
-                match self.cli.path {
-                    Some(ref cliopath) => self.data.path = Some(cliopath.path().to_owned()),
-                    None => self.data.path = None,
-                }
+                self.data.path = self.cli.file_path();
             }
         }
     }
@@ -92,6 +142,15 @@ impl App {
         self.running = false;
     }
 
+    /// The table as csv for stdout, `None` while loading or when loading failed or was cancelled.
+    pub fn stdout_csv(&self) -> FrameResult<Option<Vec<u8>>> {
+        if self.loading.is_some() || self.cancelled || self.io_error.is_some() {
+            return Ok(None);
+        }
+        let (bytes, _) = self.data.csv_description().to_csv_bytes()?;
+        Ok(Some(bytes))
+    }
+
     pub fn save(&mut self) {
         self.io_command_sender
             .send(self.data.save_csv_command())
@@ -99,29 +158,47 @@ impl App {
     }
 
     pub fn save_as_toml(&mut self) {
+        if !self.data.check_export() {
+            return;
+        }
         self.io_command_sender
             .send(self.data.save_toml_command())
             .expect("IoCommand Receiver Closed. Quitting");
     }
 
     pub fn save_as_json(&mut self) {
+        if !self.data.check_export() {
+            return;
+        }
         self.io_command_sender
             .send(self.data.save_json_command())
             .expect("IoCommand Receiver Closed. Quitting");
     }
 
     pub fn save_as_yml(&mut self) {
+        if !self.data.check_export() {
+            return;
+        }
         self.io_command_sender
             .send(self.data.save_yml_command())
             .expect("IoCommand Receiver Closed. Quitting");
     }
 
     pub fn save_as_ron(&mut self) {
+        if !self.data.check_export() {
+            return;
+        }
         self.io_command_sender
             .send(self.data.save_ron_command())
             .expect("IoCommand Receiver Closed. Quitting");
     }
 
+    pub fn save_as_json_schema(&mut self) {
+        self.io_command_sender
+            .send(self.data.save_json_schema_command())
+            .expect("IoCommand Receiver Closed. Quitting");
+    }
+
     pub fn save_key_bindings(&self) {
         self.io_command_sender
             .send(IoCommand::SaveKeyBindings(self.key_bindings.clone()))
@@ -5,7 +5,7 @@ use tracing::info;
 
 use crate::app::App;
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub enum Action {
     Quit,
     EditCell,
@@ -13,6 +13,7 @@ pub enum Action {
     EditFileName,
     EditColumnDataType,
     ApplyEdit,
+    ApplyEditKeepInvalid,
     CancelEdit,
     NextSuggestion,
     PreviousSuggestion,
@@ -30,10 +31,25 @@ pub enum Action {
     MoveColumnLeft,
     DeleteRow,
     DeleteColumn,
+    PromoteRowToHeaders,
+    DemoteHeadersToRow,
     MoveRowUp,
     MoveRowDown,
     SortByColumn,
     SortByColumnReversed,
+    ToggleFreezeColumns,
+    ToggleWrapCells,
+    ShrinkColumn,
+    GrowColumn,
+    EditColumns,
+    HideColumn,
+    ShowAllColumns,
+    ToggleColumnVisibility,
+    MoveViewColumnUp,
+    MoveViewColumnDown,
+    ToggleKeepLayout,
+    ToggleKeepSchema,
+    ValidateAll,
     ConfirmSelectCellRight,
     ConfirmSelectCellLeft,
     ConfirmSelectCellUp,
@@ -45,6 +61,34 @@ pub enum Action {
     SaveJson,
     SaveYml,
     SaveRon,
+    SaveJsonSchema,
+    CancelLoad,
+}
+
+impl Action {
+    /// Whether the action leaves the table alone, only these run while a file
+    /// is loading or after its loading was cancelled.
+    fn allowed_while_loading(&self) -> bool {
+        use Action::*;
+        matches!(
+            self,
+            Quit | CancelLoad
+                | ToggleKeyBindingsDisplay
+                | SelectCellRight
+                | SelectCellLeft
+                | SelectCellUp
+                | SelectCellDown
+                | SelectFirstRow
+                | SelectLastRow
+        )
+    }
+    /// Whether the action exports rows keyed by column name, see [`DataTable::check_export`].
+    ///
+    /// [`DataTable::check_export`]: crate::app::component_table::DataTable::check_export
+    fn is_export(&self) -> bool {
+        use Action::*;
+        matches!(self, SaveToml | SaveJson | SaveYml | SaveRon)
+    }
 }
 
 impl std::fmt::Display for Action {
@@ -56,13 +100,25 @@ impl std::fmt::Display for Action {
 impl App {
     pub fn perform_action(&mut self, action: Action) {
         use Action::*;
+        if (self.loading.is_some() || self.cancelled) && !action.allowed_while_loading() {
+            return;
+        }
+        // a warned export only goes ahead when it is repeated right away
+        if !action.is_export() {
+            self.data.export_warned = false;
+        }
         match action {
             Quit => self.quit(),
             EditCell => self.data.edit_cell(),
             EditHeader => self.data.edit_column_name(),
             EditFileName => self.data.edit_file_name(),
-            EditColumnDataType => self.data.edit_dtype_column(),
+            EditColumnDataType => {
+                self.data.dtype_select.keep_invalid_key =
+                    self.key_bindings.edit_key(&ApplyEditKeepInvalid);
+                self.data.edit_dtype_column();
+            }
             ApplyEdit => self.data.apply_edit(),
+            ApplyEditKeepInvalid => self.data.apply_edit_keep_invalid(),
             CancelEdit => self.data.edit_cancel(),
             NextSuggestion => self.data.skim_select_next(),
             PreviousSuggestion => self.data.skim_select_previous(),
@@ -82,8 +138,23 @@ impl App {
             MoveColumnLeft => self.data.move_column_left(),
             DeleteRow => self.data.delete_row(),
             DeleteColumn => self.data.delete_column(),
+            PromoteRowToHeaders => self.data.promote_row_to_headers(),
+            DemoteHeadersToRow => self.data.demote_headers_to_row(),
             SortByColumn => self.data.sort_by_column(),
             SortByColumnReversed => self.data.sort_by_column_reversed(),
+            ToggleFreezeColumns => self.data.toggle_freeze_columns(),
+            ToggleWrapCells => self.data.toggle_wrap_cells(),
+            ShrinkColumn => self.data.shrink_column(),
+            GrowColumn => self.data.grow_column(),
+            EditColumns => self.data.edit_columns(),
+            HideColumn => self.data.hide_column(),
+            ShowAllColumns => self.data.show_all_columns(),
+            ToggleColumnVisibility => self.data.toggle_column_visibility(),
+            MoveViewColumnUp => self.data.move_view_column_up(),
+            MoveViewColumnDown => self.data.move_view_column_down(),
+            ToggleKeepLayout => self.data.toggle_keep_layout(),
+            ToggleKeepSchema => self.data.toggle_keep_schema(),
+            ValidateAll => self.data.validate_all(),
             ConfirmSelectCellRight => {
                 self.data.apply_edit();
                 self.data.select_cell_right();
@@ -107,6 +178,8 @@ impl App {
             SaveJson => self.save_as_json(),
             SaveYml => self.save_as_yml(),
             SaveRon => self.save_as_ron(),
+            SaveJsonSchema => self.save_as_json_schema(),
+            CancelLoad => self.cancel_load(),
         }
     }
 }
@@ -8,8 +8,11 @@ impl App {
         // info!("{:#?}", io_event);
         use IoEvent::*;
         match io_event {
+            LoadProgress(progress) => self.load_progress(progress),
+            LoadPreview(df) => self.load_preview(df),
             LoadedCsv(parsed) => self.from_parsed_csv(parsed),
-            SavedCsv => self.data.is_dirty = false,
+            SavedCsv(notes) => self.data.saved(notes),
+            SaveFailed(e) => self.data.save_failed(e),
             LoadedKeybindings(key_bindings) => self.set_key_bindings(key_bindings),
         }
     }
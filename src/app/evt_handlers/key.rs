@@ -1,4 +1,4 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 #[allow(unused)]
 use tracing::info;
@@ -29,7 +29,11 @@ impl App {
     }
 
     fn intercept_edits(&mut self, key_event: &KeyEvent) {
+        // ctrl or alt chars are bindings, both together are AltGr text on some platforms
+        let modifiers = key_event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        let bound = !modifiers.is_empty() && modifiers != KeyModifiers::CONTROL | KeyModifiers::ALT;
         match key_event.code {
+            KeyCode::Char(_) if bound => {}
             KeyCode::Char(c) => self.data.insert_char(c),
             KeyCode::Backspace => self.data.delete_backwards(),
             KeyCode::Delete => self.data.delete_forwards(),
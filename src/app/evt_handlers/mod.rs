@@ -1,6 +1,7 @@
 mod action;
 mod io;
 mod key;
+mod mouse;
 pub use action::Action;
 
 use crate::backend::tasks::events::BackendEvent;
@@ -12,7 +13,7 @@ impl App {
         match backend_event {
             BackendEvent::Tick => self.tick(),
             BackendEvent::Key(key_event) => self.handle_key_events(key_event),
-            BackendEvent::Mouse(_) => {}
+            BackendEvent::Mouse(mouse_event) => self.handle_mouse_events(mouse_event),
             BackendEvent::Resize(_, _) => {}
             BackendEvent::IoEvent(io_event) => self.handle_io_events(io_event),
         }
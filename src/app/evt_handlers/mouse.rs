@@ -0,0 +1,16 @@
+use crossterm::event::MouseEvent;
+
+#[allow(unused)]
+use tracing::info;
+
+use crate::app::{component_table::EditTarget, App};
+
+impl App {
+    pub fn handle_mouse_events(&mut self, mouse_event: MouseEvent) {
+        if let EditTarget::ColumnManager = self.data.edit_target {
+            self.data
+                .column_manager
+                .handle_mouse(mouse_event, &mut self.data.column_view);
+        }
+    }
+}
@@ -33,6 +33,12 @@ impl From<toml::de::Error> for IoCommandError {
     }
 }
 
+impl From<toml::ser::Error> for IoCommandError {
+    fn from(value: toml::ser::Error) -> Self {
+        Self::Toml(value.to_string())
+    }
+}
+
 impl From<serde_yml::Error> for IoCommandError {
     fn from(value: serde_yml::Error) -> Self {
         Self::Yml(value.to_string())
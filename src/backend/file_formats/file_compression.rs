@@ -0,0 +1,88 @@
+use std::{
+    ffi::OsString,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use flate2::{read::MultiGzDecoder, write::GzEncoder};
+
+/// How a file is compressed, kept to write it back the same way.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// The compression the extension of `path` names, like `data.csv.gz`.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("gz") => Compression::Gzip,
+            Some("zst") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+
+    /// The compression `sample`, the start of a file, is in by its magic bytes.
+    pub fn sniff(sample: &[u8]) -> Self {
+        if sample.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if sample.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some("gz"),
+            Compression::Zstd => Some("zst"),
+        }
+    }
+
+    /// Reads `reader` decompressed.
+    pub fn decoder<R: Read + Send + 'static>(&self, reader: R) -> io::Result<Box<dyn Read + Send>> {
+        Ok(match self {
+            Compression::None => Box::new(reader),
+            Compression::Gzip => Box::new(MultiGzDecoder::new(reader)),
+            Compression::Zstd => Box::new(zstd::Decoder::new(reader)?),
+        })
+    }
+
+    pub fn compress(&self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Compression::None => Ok(bytes.to_vec()),
+            Compression::Gzip => {
+                let mut encoder = GzEncoder::new(vec![], flate2::Compression::default());
+                encoder.write_all(bytes)?;
+                encoder.finish()
+            }
+            Compression::Zstd => zstd::encode_all(bytes, 0),
+        }
+    }
+
+    /// `path` with its extension, the one before a compression extension,
+    /// set to `extension`, and the extension of this compression after it.
+    ///
+    /// `data.csv.gz` + `json` -> `data.json.gz`
+    pub fn with_extension(&self, path: &Path, extension: &str) -> PathBuf {
+        let mut path = match Compression::from_path(path) {
+            Compression::None => path.to_owned(),
+            _ => path.with_extension(""),
+        };
+        path.set_extension(extension);
+        match self.extension() {
+            Some(compressed) => {
+                let mut name = OsString::from(path);
+                name.push(".");
+                name.push(compressed);
+                PathBuf::from(name)
+            }
+            None => path,
+        }
+    }
+}
@@ -1,8 +1,27 @@
-use std::path::PathBuf;
+use std::{
+    cell::Cell,
+    fs::File,
+    io::{self, BufRead, BufReader, Cursor, Read},
+    ops::ControlFlow,
+    path::PathBuf,
+    rc::Rc,
+};
+
+use encoding_rs::{Encoding, UTF_8};
 
 use crate::{
-    backend::{utils::read_file, IoCommandError, IoCommandResult},
-    dataframe::DataFrame,
+    backend::{IoCommandError, IoCommandResult},
+    dataframe::{
+        csv::{CsvDialect, CsvDialectHint, CsvProgress, CsvReadOptions, CsvWriteOptions},
+        DataFrame, FrameResult,
+    },
+};
+
+use super::{
+    file_compression::Compression,
+    file_encoding::{FileEncoding, StrictUtf8, SNIFF_BYTES},
+    file_layout::{load_layout, ColumnLayout},
+    file_schema::load_schema,
 };
 
 #[derive(Default, Debug, Clone)]
@@ -16,29 +35,146 @@ pub struct CsvDescription {
     pub df: DataFrame,
     pub errors: Vec<String>,
     pub path: Option<PathBuf>,
-    pub delim: char,
+    pub dialect: CsvDialect,
+    /// The encoding the file was read in and is saved in.
+    pub encoding: FileEncoding,
+    /// The compression the file was read with and is saved with.
+    pub compression: Compression,
+    pub layout: Option<ColumnLayout>,
+    /// Whether a save writes the schema sidecar, set when the file came with one.
+    pub keep_schema: bool,
 }
 
-pub async fn load_csv(path: PathBuf, delim: char) -> IoCommandResult<CsvDescription> {
-    let res = read_file(&path).await;
-    match res {
-        Err(e) => Err(IoCommandError::FileIo {
-            path,
-            error: e.to_string(),
-        }),
-        Ok(res) => {
-            let data_frame_csv_result = DataFrame::parsed_from_csv(&res, delim)?;
-
-            Ok(CsvDescription {
-                path: Some(path),
-                delim,
-                df: data_frame_csv_result.df,
-                errors: data_frame_csv_result.errors,
-            })
-        }
+impl CsvDescription {
+    /// The frame as csv in the dialect and encoding it was read in, or as UTF-8
+    /// with a note for the user if the encoding lacks some of its characters.
+    pub fn to_csv_bytes(&self) -> FrameResult<(Vec<u8>, Option<String>)> {
+        let options = CsvWriteOptions {
+            dialect: self.dialect,
+            crlf: self.encoding.crlf,
+        };
+        let content = DataFrame::to_csv_with(&self.df, options)?;
+        Ok(match self.encoding.encode(&content) {
+            Some(bytes) => (bytes, None),
+            None => {
+                let note = format!(
+                    "Saved as UTF-8, the table holds characters {} cannot represent",
+                    self.encoding.name()
+                );
+                (content.into_bytes(), Some(note))
+            }
+        })
     }
 }
 
+/// Streams the csv at `path`, or stdin if there is none, on a blocking thread,
+/// see [`DataFrame::parsed_from_csv_reader`].
+///
+/// The encoding and the parts of the dialect not in `hint` are detected unless
+/// given, `progress` also gets the size of the file, if known. A file guessed
+/// to be UTF-8 with malformed bytes past the sample is read again in the
+/// encoding those bytes suggest.
+pub async fn load_csv(
+    path: Option<PathBuf>,
+    hint: CsvDialectHint,
+    encoding: Option<&'static Encoding>,
+    options: CsvReadOptions,
+    mut progress: impl FnMut(CsvProgress, Option<u64>, &DataFrame) -> ControlFlow<()> + Send + 'static,
+) -> IoCommandResult<CsvDescription> {
+    let schema = match path {
+        Some(ref path) => load_schema(path).await?,
+        None => None,
+    };
+    let keep_schema = schema.is_some();
+    let source = path.clone();
+    // reading stdin waits on the writer of the pipe, so even sniffing is not done here
+    let (dialect, encoding, compression, data_frame_csv_result) =
+        tokio::task::spawn_blocking(move || {
+            let file_io = |e: io::Error| IoCommandError::FileIo {
+                path: source.clone().unwrap_or_else(|| PathBuf::from("-")),
+                error: e.to_string(),
+            };
+            let mut forced = encoding;
+            loop {
+                let (input, total): (Box<dyn Read + Send>, _) = match source {
+                    Some(ref path) => {
+                        let file = File::open(path).map_err(file_io)?;
+                        let total = file.metadata().ok().map(|m| m.len());
+                        (Box::new(file), total)
+                    }
+                    None => (Box::new(io::stdin()), None),
+                };
+                let (input, magic) = sample(input).map_err(file_io)?;
+                let compression = Compression::sniff(&magic);
+                // the size of a compressed file says little about the rows in it
+                let total = total.filter(|_| compression == Compression::None);
+                let input = compression.decoder(input).map_err(file_io)?;
+                let (input, _) = sample(input).map_err(file_io)?;
+                let mut reader = BufReader::with_capacity(SNIFF_BYTES, input);
+                let encoding = FileEncoding::sniff(&mut reader, forced).map_err(file_io)?;
+                let sample = reader.fill_buf().map_err(file_io)?;
+                let (sample, _) = encoding.encoding.decode_with_bom_removal(sample);
+                let dialect = CsvDialect::sniff(&options.data_lines(&sample), hint);
+                // UTF-8 is guessed from the sample, the rest of the file can prove it wrong
+                let misread = Rc::new(Cell::new(None));
+                let input: Box<dyn Read> =
+                    match forced.is_none() && !encoding.bom && encoding.encoding == UTF_8 {
+                        true => Box::new(StrictUtf8::new(reader, misread.clone())),
+                        false => Box::new(reader),
+                    };
+                let parsed = DataFrame::parsed_from_csv_reader(
+                    encoding.decoder(input),
+                    dialect,
+                    schema.as_ref(),
+                    options,
+                    |read, df| progress(read, total, df),
+                )?;
+                match misread.get() {
+                    None => return IoCommandResult::Ok((dialect, encoding, compression, parsed)),
+                    // read the file again in the encoding its malformed bytes suggest
+                    Some(guess) if source.is_some() => forced = Some(guess),
+                    Some(guess) => {
+                        return Err(IoCommandError::Io(format!(
+                            "stdin is not UTF-8 past its first {} KiB, it looks like {}, \
+                            read it with --encoding",
+                            SNIFF_BYTES / 1024,
+                            guess.name()
+                        )))
+                    }
+                }
+            }
+        })
+        .await
+        .map_err(|e| IoCommandError::Io(e.to_string()))??;
+    let layout = match path {
+        Some(ref path) => load_layout(path).await,
+        None => None,
+    };
+
+    Ok(CsvDescription {
+        path,
+        dialect,
+        encoding,
+        compression,
+        layout,
+        keep_schema,
+        df: data_frame_csv_result.df,
+        errors: data_frame_csv_result.errors,
+    })
+}
+
+/// `input` with its first [`SNIFF_BYTES`] read ahead, and those bytes.
+///
+/// A pipe hands out what was written so far, the sample waits for more.
+fn sample(mut input: Box<dyn Read + Send>) -> io::Result<(Box<dyn Read + Send>, Vec<u8>)> {
+    let mut sample = vec![];
+    input
+        .by_ref()
+        .take(SNIFF_BYTES as u64)
+        .read_to_end(&mut sample)?;
+    Ok((Box::new(Cursor::new(sample.clone()).chain(input)), sample))
+}
+
 // impl CsvDescription {
 //     pub fn data_to_string(&self) -> AppResult<String> {
 //         let mut wtr = WriterBuilder::new()
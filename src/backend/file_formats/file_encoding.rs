@@ -0,0 +1,157 @@
+use std::{
+    cell::Cell,
+    io::{self, BufRead, BufReader, Read},
+    rc::Rc,
+};
+
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use encoding_rs_io::DecodeReaderBytesBuilder;
+
+/// Bytes looked at to guess the encoding and line endings of a file.
+pub const SNIFF_BYTES: usize = 64 * 1024;
+
+/// How a text file is stored, kept to write it back the same way.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FileEncoding {
+    pub encoding: &'static Encoding,
+    /// The file starts with a byte order mark.
+    pub bom: bool,
+    /// Lines end with `\r\n`.
+    pub crlf: bool,
+}
+
+impl Default for FileEncoding {
+    fn default() -> Self {
+        Self {
+            encoding: UTF_8,
+            bom: false,
+            crlf: false,
+        }
+    }
+}
+
+impl FileEncoding {
+    /// Guesses the encoding from the start of `reader`, `forced` is used instead
+    /// unless the file starts with a byte order mark.
+    pub fn sniff<R: Read>(
+        reader: &mut BufReader<R>,
+        forced: Option<&'static Encoding>,
+    ) -> io::Result<Self> {
+        let sample = reader.fill_buf()?;
+        let (encoding, bom) = match Encoding::for_bom(sample) {
+            Some((encoding, _)) => (encoding, true),
+            None => (forced.unwrap_or_else(|| detect(sample)), false),
+        };
+        let (text, _) = encoding.decode_with_bom_removal(sample);
+        let crlf = text.find('\n').is_some_and(|i| text[..i].ends_with('\r'));
+        Ok(Self {
+            encoding,
+            bom,
+            crlf,
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.encoding.name()
+    }
+
+    /// Reads `reader` as UTF-8, without the byte order mark.
+    pub fn decoder<R: Read>(&self, reader: R) -> impl Read {
+        DecodeReaderBytesBuilder::new()
+            .encoding(Some(self.encoding))
+            .build(reader)
+    }
+
+    /// `text` in this encoding, `None` if it holds characters the encoding lacks.
+    pub fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        let mut bytes = match self.bom {
+            true => bom(self.encoding).to_vec(),
+            false => vec![],
+        };
+        if self.encoding == UTF_16LE {
+            bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        } else if self.encoding == UTF_16BE {
+            bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+        } else {
+            let (encoded, _, unmappable) = self.encoding.encode(text);
+            if unmappable {
+                return None;
+            }
+            bytes.extend_from_slice(&encoded);
+        }
+        Some(bytes)
+    }
+}
+
+/// Passes a file guessed to be UTF-8 through, ending it at the first malformed bytes
+/// instead of having them replaced, the encoding those bytes suggest is left in `misread`.
+pub struct StrictUtf8<R> {
+    inner: R,
+    /// The start of a character cut off by the end of the last read.
+    pending: Vec<u8>,
+    misread: Rc<Cell<Option<&'static Encoding>>>,
+}
+
+impl<R: Read> StrictUtf8<R> {
+    pub fn new(inner: R, misread: Rc<Cell<Option<&'static Encoding>>>) -> Self {
+        Self {
+            inner,
+            pending: vec![],
+            misread,
+        }
+    }
+}
+
+impl<R: Read> Read for StrictUtf8<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.misread.get().is_some() {
+            return Ok(0);
+        }
+        let n = self.inner.read(buf)?;
+        let mut bytes = std::mem::take(&mut self.pending);
+        bytes.extend_from_slice(&buf[..n]);
+        match std::str::from_utf8(&bytes) {
+            Ok(_) => Ok(n),
+            Err(e) if e.error_len().is_none() && n > 0 => {
+                self.pending = bytes.split_off(e.valid_up_to());
+                Ok(n)
+            }
+            Err(_) => {
+                self.misread.set(Some(detect(&bytes)));
+                Ok(0)
+            }
+        }
+    }
+}
+
+/// UTF-8 if `sample` is valid UTF-8, the most likely legacy encoding otherwise.
+fn detect(sample: &[u8]) -> &'static Encoding {
+    match std::str::from_utf8(sample) {
+        Ok(_) => UTF_8,
+        // the sample ends inside a character
+        Err(e) if e.error_len().is_none() => UTF_8,
+        Err(_) => {
+            let mut detector = EncodingDetector::new();
+            detector.feed(sample, true);
+            detector.guess(None, false)
+        }
+    }
+}
+
+fn bom(encoding: &'static Encoding) -> &'static [u8] {
+    if encoding == UTF_16LE {
+        b"\xFF\xFE"
+    } else if encoding == UTF_16BE {
+        b"\xFE\xFF"
+    } else if encoding == UTF_8 {
+        b"\xEF\xBB\xBF"
+    } else {
+        b""
+    }
+}
+
+/// Parses an encoding name like `latin1` or `windows-1252`.
+pub fn parse_encoding(label: &str) -> Result<&'static Encoding, String> {
+    Encoding::for_label(label.as_bytes()).ok_or_else(|| format!("unknown encoding '{label}'"))
+}
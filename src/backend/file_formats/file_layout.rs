@@ -0,0 +1,45 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::{
+    utils::{read_file, sidecar_path},
+    IoCommandResult,
+};
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutColumn {
+    pub name: String,
+    pub visible: bool,
+    /// Width the column was shrunk or grown to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_width: Option<u16>,
+}
+
+/// Column order, visibility and widths of the table view, kept next to the csv file.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnLayout {
+    #[serde(default)]
+    pub frozen: usize,
+    pub columns: Vec<LayoutColumn>,
+}
+
+impl ColumnLayout {
+    pub fn to_toml_string(&self) -> IoCommandResult<String> {
+        Ok(toml::to_string(self)?)
+    }
+}
+
+/// `data.csv` -> `data.csv.layout.toml`
+pub fn layout_path(path: &Path) -> PathBuf {
+    sidecar_path(path, "layout.toml")
+}
+
+pub async fn load_layout(path: &Path) -> Option<ColumnLayout> {
+    let path = layout_path(path);
+    if !path.try_exists().unwrap_or(false) {
+        return None;
+    }
+    let text = read_file(&path).await.ok()?;
+    toml::from_str(&text).ok()
+}
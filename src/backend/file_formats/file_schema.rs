@@ -0,0 +1,27 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    backend::{
+        utils::{read_file, sidecar_path},
+        IoCommandResult,
+    },
+    dataframe::Schema,
+};
+
+/// `data.csv` -> `data.csv.schema.toml`
+pub fn schema_path(path: &Path) -> PathBuf {
+    sidecar_path(path, "schema.toml")
+}
+
+pub async fn load_schema(path: &Path) -> IoCommandResult<Option<Schema>> {
+    let path = schema_path(path);
+    if !path.try_exists().unwrap_or(false) {
+        return Ok(None);
+    }
+    let text = read_file(&path).await?;
+    Ok(Some(toml::from_str(&text)?))
+}
+
+pub fn schema_to_toml_string(schema: &Schema) -> IoCommandResult<String> {
+    Ok(toml::to_string(schema)?)
+}
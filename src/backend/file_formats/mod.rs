@@ -1,2 +1,6 @@
+pub mod file_compression;
 pub mod file_csv;
+pub mod file_encoding;
+pub mod file_layout;
 pub mod file_multi;
+pub mod file_schema;
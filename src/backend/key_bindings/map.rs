@@ -57,6 +57,14 @@ impl KeyBindings {
         Ok(())
     }
 
+    /// The first edit mode key of `action`, as shown to the user.
+    pub fn edit_key(&self, action: &Action) -> Option<String> {
+        self.edit
+            .iter()
+            .find(|(_, a)| *a == action)
+            .map(|(k, _)| KeyCombinationFormat::default().to_string(k.to_owned()))
+    }
+
     pub fn display(&self) -> (Vec<[String; 2]>, Vec<[String; 2]>) {
         let format = KeyCombinationFormat::default();
         let normal = self
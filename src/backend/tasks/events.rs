@@ -1,11 +1,18 @@
 use std::path::PathBuf;
 
 use crossterm::event::{KeyEvent, MouseEvent};
+use encoding_rs::Encoding;
 
-use crate::backend::{
-    file_formats::{file_csv::CsvDescription, file_multi::FileDescription},
-    key_bindings::KeyBindings,
-    IoCommandResult,
+use crate::{
+    backend::{
+        file_formats::{file_csv::CsvDescription, file_multi::FileDescription},
+        key_bindings::KeyBindings,
+        IoCommandError, IoCommandResult,
+    },
+    dataframe::{
+        csv::{CsvDialectHint, CsvReadOptions},
+        DataFrame,
+    },
 };
 
 #[derive(Clone, Debug)]
@@ -20,18 +27,63 @@ pub enum BackendEvent {
 #[derive(Clone, Debug)]
 pub enum IoCommand {
     SaveCsv(CsvDescription),
-    LoadCsv { path: PathBuf, delim: char },
+    LoadCsv {
+        /// `None` reads stdin.
+        path: Option<PathBuf>,
+        /// Sniffed where not given.
+        dialect: CsvDialectHint,
+        /// Detected when not given.
+        encoding: Option<&'static Encoding>,
+        options: CsvReadOptions,
+    },
+    /// Stops the running csv load.
+    CancelLoad,
     LoadKeyBindings,
     SaveKeyBindings(KeyBindings),
     SaveToml(FileDescription),
     SaveJson(FileDescription),
     SaveYml(FileDescription),
     SaveRon(FileDescription),
+    SaveJsonSchema {
+        path: PathBuf,
+        schema: serde_json::Value,
+    },
 }
 
 #[derive(Clone, Debug)]
 pub enum IoEvent {
+    LoadProgress(LoadProgress),
+    /// The first rows of a csv that is still loading.
+    LoadPreview(DataFrame),
     LoadedCsv(IoCommandResult<CsvDescription>),
-    SavedCsv,
+    /// Notes on how the file was saved, for the user.
+    SavedCsv(Vec<String>),
+    /// Saving the csv or an export failed, the table stays unsaved.
+    SaveFailed(IoCommandError),
     LoadedKeybindings(IoCommandResult<KeyBindings>),
 }
+
+/// How far loading a csv file has come.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct LoadProgress {
+    pub bytes: u64,
+    /// Size of the file, if known.
+    pub total: Option<u64>,
+    pub rows: usize,
+}
+
+impl std::fmt::Display for LoadProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mb = |bytes: u64| bytes as f64 / 1_000_000.0;
+        write!(f, "Loading: {} rows, {:.1} MB", self.rows, mb(self.bytes))?;
+        if let Some(total) = self.total.filter(|t| *t > 0) {
+            write!(
+                f,
+                " of {:.1} MB ({}%)",
+                mb(total),
+                (self.bytes * 100 / total).min(100)
+            )?;
+        }
+        Ok(())
+    }
+}
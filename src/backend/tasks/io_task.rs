@@ -1,4 +1,11 @@
-use std::path::PathBuf;
+use std::{
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 use tokio::sync::mpsc;
 
@@ -7,10 +14,19 @@ use tracing::info;
 
 use crate::{
     backend::{
-        file_formats::file_csv::load_csv, key_bindings::KeyBindings, tasks::events::IoEvent,
+        file_formats::{
+            file_compression::Compression,
+            file_csv::{load_csv, CsvDescription},
+            file_layout::layout_path,
+            file_schema::{schema_path, schema_to_toml_string},
+        },
+        key_bindings::KeyBindings,
+        tasks::events::{IoEvent, LoadProgress},
         utils::save_file,
+        IoCommandError, IoCommandResult,
     },
-    dataframe::DataFrame,
+    dataframe::{csv::CsvProgress, DataFrame},
+    AppResult,
 };
 
 use super::events::{BackendEvent, IoCommand};
@@ -19,6 +35,8 @@ pub async fn io_task(
     event_sender: mpsc::UnboundedSender<BackendEvent>,
     mut io_command_receiver: mpsc::UnboundedReceiver<IoCommand>,
 ) {
+    // set to stop the running csv load
+    let mut cancel_load: Option<Arc<AtomicBool>> = None;
     loop {
         tokio::select! {
             _ = event_sender.closed() => {
@@ -27,15 +45,28 @@ pub async fn io_task(
             Some(io_task) = io_command_receiver.recv() => {
                 // info!("{:#?}", io_task);
                 match io_task {
-                    IoCommand::LoadCsv{path, delim} => {
-                        let parsed = load_csv(path, delim).await;
-                        let evt = IoEvent::LoadedCsv(parsed);
-                        event_sender.send(BackendEvent::IoEvent(evt)).unwrap();
+                    IoCommand::LoadCsv{path, dialect, encoding, options} => {
+                        let cancel = Arc::new(AtomicBool::new(false));
+                        cancel_load = Some(cancel.clone());
+                        let event_sender = event_sender.clone();
+                        tokio::spawn(async move {
+                            let progress = load_progress(event_sender.clone(), cancel);
+                            let parsed = load_csv(path, dialect, encoding, options, progress).await;
+                            let evt = IoEvent::LoadedCsv(parsed);
+                            event_sender.send(BackendEvent::IoEvent(evt)).unwrap();
+                        });
+                    },
+                    IoCommand::CancelLoad => {
+                        if let Some(cancel) = cancel_load.take() {
+                            cancel.store(true, Ordering::Relaxed);
+                        }
                     },
                     IoCommand::SaveCsv(data) => {
-                        let content = DataFrame::to_csv(&data.df).unwrap();
-                        save_file(&data.path.unwrap_or(PathBuf::from("export.csv")), &content).await.unwrap();
-                        event_sender.send(BackendEvent::IoEvent(IoEvent::SavedCsv)).unwrap();
+                        let evt = match save_csv(data).await {
+                            Ok(notes) => IoEvent::SavedCsv(notes),
+                            Err(e) => IoEvent::SaveFailed(e),
+                        };
+                        event_sender.send(BackendEvent::IoEvent(evt)).unwrap();
                     }
                     IoCommand::LoadKeyBindings => {
                         let key_bindings = KeyBindings::load().await;
@@ -43,26 +74,104 @@ pub async fn io_task(
                         event_sender.send(BackendEvent::IoEvent(evt)).unwrap()
                     }
                     IoCommand::SaveKeyBindings(key_bindings) => {
-                        key_bindings.save().await.unwrap();
+                        let saved = key_bindings.save().await;
+                        report_failure(&event_sender, saved);
                     }
                     IoCommand::SaveToml(data) => {
-                        let data_string = data.to_toml_string().unwrap();
-                        save_file(&data.path, &data_string).await.unwrap();
+                        let content = exported(data.to_toml_string());
+                        report_failure(&event_sender, export(&data.path, content).await);
                     }
                     IoCommand::SaveJson(data) => {
-                        let data_string = data.to_json_string().unwrap();
-                        save_file(&data.path, &data_string).await.unwrap();
+                        let content = exported(data.to_json_string());
+                        report_failure(&event_sender, export(&data.path, content).await);
                     }
                     IoCommand::SaveYml(data) => {
-                        let data_string = data.to_yml_string().unwrap();
-                        save_file(&data.path, &data_string).await.unwrap();
+                        let content = exported(data.to_yml_string());
+                        report_failure(&event_sender, export(&data.path, content).await);
                     }
                     IoCommand::SaveRon(data) => {
-                        let data_string = data.to_ron_string().unwrap();
-                        save_file(&data.path, &data_string).await.unwrap();
+                        let content = exported(data.to_ron_string());
+                        report_failure(&event_sender, export(&data.path, content).await);
+                    }
+                    IoCommand::SaveJsonSchema{path, schema} => {
+                        let content = serde_json::to_string_pretty(&schema)
+                            .map_err(|e| IoCommandError::Io(e.to_string()));
+                        let saved = match content {
+                            Ok(content) => save_file(&path, &content).await,
+                            Err(e) => Err(e),
+                        };
+                        report_failure(&event_sender, saved);
                     }
                 }
             }
         }
     }
 }
+
+/// Writes the csv file with its schema and layout sidecars, returns notes for the user.
+async fn save_csv(data: CsvDescription) -> IoCommandResult<Vec<String>> {
+    let (bytes, note) = data.to_csv_bytes()?;
+    let path = data
+        .path
+        .unwrap_or_else(|| data.compression.with_extension(Path::new("export"), "csv"));
+    save_file(&path, data.compression.compress(&bytes)?).await?;
+    if data.keep_schema {
+        let schema_string = schema_to_toml_string(&data.df.schema())?;
+        save_file(&schema_path(&path), &schema_string).await?;
+    }
+    if let Some(layout) = data.layout {
+        save_file(&layout_path(&path), layout.to_toml_string()?).await?;
+    }
+    Ok(note.into_iter().collect())
+}
+
+/// An export as text, formats fail on values they can't hold, like integers past 64 bit in toml.
+fn exported(content: AppResult<String>) -> IoCommandResult<String> {
+    content.map_err(|e| IoCommandError::Io(e.to_string()))
+}
+
+/// Writes `content` to `path`, compressed as its extension says, exports of compressed tables are compressed.
+async fn export(path: &PathBuf, content: IoCommandResult<String>) -> IoCommandResult<()> {
+    let bytes = Compression::from_path(path).compress(content?.as_bytes())?;
+    save_file(path, bytes).await
+}
+
+/// Tells the app about a failed save, successful ones need no answer.
+fn report_failure(event_sender: &mpsc::UnboundedSender<BackendEvent>, saved: IoCommandResult<()>) {
+    if let Err(e) = saved {
+        event_sender
+            .send(BackendEvent::IoEvent(IoEvent::SaveFailed(e)))
+            .unwrap();
+    }
+}
+
+/// Reports the progress of a csv load, the first report also sends the rows read so far.
+fn load_progress(
+    event_sender: mpsc::UnboundedSender<BackendEvent>,
+    cancel: Arc<AtomicBool>,
+) -> impl FnMut(CsvProgress, Option<u64>, &DataFrame) -> ControlFlow<()> + Send + 'static {
+    let mut previewed = false;
+    move |read, total, df| {
+        if cancel.load(Ordering::Relaxed) {
+            return ControlFlow::Break(());
+        }
+        let progress = LoadProgress {
+            bytes: read.bytes,
+            total,
+            rows: read.rows,
+        };
+        let mut events = vec![IoEvent::LoadProgress(progress)];
+        if !previewed {
+            previewed = true;
+            events.push(IoEvent::LoadPreview(df.clone()));
+        }
+        // a closed channel means the app is gone
+        let sent = events
+            .into_iter()
+            .all(|evt| event_sender.send(BackendEvent::IoEvent(evt)).is_ok());
+        match sent {
+            true => ControlFlow::Continue(()),
+            false => ControlFlow::Break(()),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 use super::IoCommandResult;
@@ -9,8 +9,8 @@ pub async fn read_file(path: &PathBuf) -> IoCommandResult<String> {
     file.read_to_string(&mut buffer).await?;
     Ok(buffer)
 }
-pub async fn save_file(path: &PathBuf, content: &str) -> IoCommandResult<()> {
-    let data: &[u8] = content.as_bytes();
+pub async fn save_file(path: &PathBuf, content: impl AsRef<[u8]>) -> IoCommandResult<()> {
+    let data: &[u8] = content.as_ref();
     if path.extension().is_some() {
         tokio::fs::create_dir_all(path.parent().unwrap()).await?;
     }
@@ -18,3 +18,11 @@ pub async fn save_file(path: &PathBuf, content: &str) -> IoCommandResult<()> {
     file.write_all(data).await?;
     Ok(())
 }
+
+/// `data.csv` + `schema.toml` -> `data.csv.schema.toml`
+pub fn sidecar_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
@@ -1,57 +1,118 @@
 use serde::{Deserialize, Serialize};
 
 mod access;
+mod column;
+mod columns;
+mod constraints;
+mod convert;
 pub mod csv;
 mod data_row;
 mod data_type;
 mod error;
 mod export;
+mod format;
 mod header;
+mod names;
+mod records;
+mod schema;
 mod value;
+pub use column::Column;
+pub use columns::{CellMut, RowView, RowsMut};
+pub use constraints::{ConstraintViolation, Constraints, Violation};
+pub use convert::ConversionPreview;
 pub use data_row::DataRow;
 pub use data_type::{DataType, DataTypeParseError};
 pub use error::{FrameError, FrameResult};
+pub use format::{Format, Literals};
 pub use header::Header;
-pub use value::{DataValue, Float};
+pub use schema::{ColumnSchema, Schema};
+pub use value::{DataValue, Float, LiteralSeed};
 
+/// A table of typed columns.
+///
+/// Cells are stored column by column, see [`Column`], rows are
+/// assembled on access as [`DataRow`]s.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "FrameRows", try_from = "FrameRows")]
 pub struct DataFrame {
+    headers: Vec<Header>,
+    columns: Vec<Column>,
+    height: usize,
+    /// The text the rows were read from, see [`csv::CsvReadOptions::round_trip`].
+    source: Option<Box<csv::SourceText>>,
+}
+
+/// The row wise form a [`DataFrame`] is serialized as.
+#[derive(Serialize, Deserialize)]
+struct FrameRows {
     headers: Vec<Header>,
     rows: Vec<DataRow>,
 }
 
+impl From<DataFrame> for FrameRows {
+    fn from(df: DataFrame) -> Self {
+        Self {
+            rows: df.rows().collect(),
+            headers: df.headers,
+        }
+    }
+}
+
+impl TryFrom<FrameRows> for DataFrame {
+    type Error = FrameError;
+    fn try_from(frame: FrameRows) -> FrameResult<Self> {
+        DataFrame::new(frame.headers, frame.rows)
+    }
+}
+
 impl Default for DataFrame {
     fn default() -> Self {
-        Self {
-            headers: vec![
+        Self::new(
+            vec![
                 Header::new("column0").with_dtype(DataType::String),
                 Header::new("column1").with_dtype(DataType::String),
             ],
-            rows: vec![DataRow::new(2)],
-        }
+            vec![DataRow::new(2)],
+        )
+        .expect("rows to fit the headers")
     }
 }
 
 impl DataFrame {
     pub fn new(headers: Vec<Header>, rows: Vec<DataRow>) -> FrameResult<Self> {
         let width = headers.len();
-        if rows.iter().all(|row| row.len() == width) {
-            Ok(Self { headers, rows })
-        } else {
-            Err(FrameError::NotUniformColumnWidths)
+        if !rows.iter().all(|row| row.len() == width) {
+            return Err(FrameError::NotUniformColumnWidths);
         }
+        let mut df = Self {
+            columns: (0..width).map(|_| Column::default()).collect(),
+            height: 0,
+            headers,
+            source: None,
+        };
+        rows.into_iter().for_each(|row| df.push_row(row));
+        Ok(df)
+    }
+    /// Appends `row`, missing cells are null and extra cells are dropped.
+    pub fn push_row(&mut self, row: DataRow) {
+        let mut values = row.into_values().into_iter();
+        self.columns
+            .iter_mut()
+            .for_each(|column| column.push(values.next().unwrap_or(DataValue::Null)));
+        self.source.iter_mut().for_each(|s| s.push());
+        self.height += 1;
     }
     pub fn width(&self) -> usize {
         self.headers.len()
     }
     pub fn height(&self) -> usize {
-        self.rows.len()
+        self.height
     }
     pub fn is_valid(&self, row: usize, col: usize) -> bool {
         self.is_valid_row(row) && self.is_valid_col(col)
     }
     pub fn is_valid_row(&self, row: usize) -> bool {
-        self.rows.len() > row
+        self.height > row
     }
     pub fn is_valid_col(&self, col: usize) -> bool {
         self.headers.len() > col
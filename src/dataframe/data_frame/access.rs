@@ -1,66 +1,113 @@
-use super::{DataFrame, DataRow, DataType, DataValue, Header};
+use super::{
+    data_type::ENUM_MAX_VALUES, CellMut, Column, DataFrame, DataRow, DataType, DataValue,
+    FrameError, FrameResult, Header, RowsMut,
+};
 use itertools::Itertools;
+use std::borrow::Cow;
 use tracing::info;
+use unicode_width::UnicodeWidthStr;
 
 impl DataFrame {
     pub fn headers(&self) -> &Vec<Header> {
         &self.headers
     }
+    /// The headers to change, the cached column widths are dropped as
+    /// a changed format prints the cells differently.
     pub fn headers_mut(&mut self) -> &mut Vec<Header> {
+        self.columns.iter_mut().for_each(Column::forget_width);
         &mut self.headers
     }
     pub fn header_get(&self, col: usize) -> Option<&Header> {
         self.headers.get(col)
     }
-    pub fn header_set(&mut self, col: usize, name: String) {
+    /// Renames column `col`, a name taken by another column gets a suffix.
+    /// Returns the name that was set.
+    pub fn header_set(&mut self, col: usize, name: String) -> String {
+        let name = self.unique_header_name(&name, Some(col));
         if let Some(header) = self.headers.get_mut(col) {
             header.set_name(&name);
         }
+        name
     }
-    pub fn rows(&self) -> &Vec<DataRow> {
-        &self.rows
+    pub fn columns(&self) -> &Vec<Column> {
+        &self.columns
     }
-    pub fn rows_mut(&mut self) -> &mut Vec<DataRow> {
-        &mut self.rows
+    /// The rows, assembled from the columns.
+    pub fn rows(&self) -> impl Iterator<Item = DataRow> + '_ {
+        (0..self.height).map(|row| self.row_get(row).expect("row to be in range"))
     }
-    pub fn get(&self, row: usize, col: usize) -> Option<&DataValue> {
-        if let Some(row) = self.rows.get(row) {
-            if let Some(value) = row.get(col) {
-                return Some(value);
-            }
-        }
-        None
+    /// The rows to change in place, written back to the columns when dropped.
+    pub fn rows_mut(&mut self) -> RowsMut<'_> {
+        RowsMut::new(self)
     }
-    pub fn get_print(&self, row: usize, col: usize) -> String {
-        if let Some(row) = self.rows.get(row) {
-            if let Some(value) = row.get(col) {
-                return value.to_string();
-            }
+    pub fn row_get(&self, row: usize) -> Option<DataRow> {
+        match self.is_valid_row(row) {
+            true => Some(
+                self.columns
+                    .iter()
+                    .map(|c| c.get(row).unwrap_or_default())
+                    .collect_vec()
+                    .into(),
+            ),
+            false => None,
         }
-        info!("{:#?}", "NEW");
-        String::new()
     }
-    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut DataValue> {
-        if let Some(row) = self.rows.get_mut(row) {
-            if let Some(value) = row.get_mut(col) {
-                return Some(value);
-            }
+    pub fn get(&self, row: usize, col: usize) -> Option<DataValue> {
+        self.columns.get(col).and_then(|c| c.get(row))
+    }
+    /// The text of a string cell, borrowed from its column.
+    pub fn get_str(&self, row: usize, col: usize) -> Option<&str> {
+        self.columns.get(col).and_then(|c| c.get_str(row))
+    }
+    /// Whether the cell holds text that did not convert to its column's type.
+    pub fn is_invalid(&self, row: usize, col: usize) -> bool {
+        self.columns.get(col).is_some_and(|c| c.is_invalid(row))
+    }
+    /// The cell to change in place, written back to its column when dropped.
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<CellMut<'_>> {
+        self.columns
+            .get_mut(col)
+            .and_then(|column| CellMut::new(column, row))
+    }
+    /// The cell as its column prints it, strings are borrowed.
+    pub fn get_print(&self, row: usize, col: usize) -> Cow<'_, str> {
+        let printed = self
+            .columns
+            .get(col)
+            .and_then(|c| c.print(row, &self.headers[col]));
+        if let Some(printed) = printed {
+            return printed;
         }
-        None
+        info!("{:#?}", "NEW");
+        Cow::default()
     }
-    pub fn parse_set(&mut self, row: usize, col: usize, value: &str) {
+    /// Parses `value` for the cell and sets it if it passes the column rules.
+    pub fn parse_set(&mut self, row: usize, col: usize, value: &str) -> FrameResult<()> {
         if self.is_valid(row, col) {
-            let mut dtype_col = self.dtype_column(col).expect("col to be in range");
+            let dtype_col = self.dtype_column(col).expect("col to be in range");
             if dtype_col == DataType::Null {
                 self.headers_mut()
                     .get_mut(col)
                     .expect("col to be valid")
                     .set_dtype(DataType::String);
-                dtype_col = DataType::String;
             }
-            let value = dtype_col.parse(value);
-            self.set(row, col, value);
+            let header = &self.headers[col];
+            let parsed = match header.parse(value) {
+                Ok(DataValue::Null) if !header.nullable() => header.default_parsed(),
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    return Err(FrameError::InvalidValue(format!(
+                        "'{}' is not a valid {}: {}",
+                        value,
+                        header.dtype(),
+                        e
+                    )))
+                }
+            };
+            self.check(row, col, &parsed)?;
+            self.columns[col].set(row, parsed);
         }
+        Ok(())
     }
     pub fn set(&mut self, row: usize, col: usize, value: impl Into<DataValue>) {
         let value: DataValue = value.into();
@@ -68,26 +115,51 @@ impl DataFrame {
         if self.is_valid(row, col) {
             let dtype_col = self.dtype_column(col).expect("col to be in range");
 
-            if dtype_col == value.dtype() {
-                let row = self.rows.get_mut(row).expect("row to be in range");
-                let v = row.get_mut(col).expect("col to be in range");
-                *v = value.clone();
+            if dtype_col.matches(&value) {
+                self.columns[col].set(row, value);
             }
         }
     }
-    pub fn column_get(&self, col: usize) -> Vec<&DataValue> {
-        self.rows
-            .iter()
-            .filter_map(|row| row.get(col))
-            .collect_vec()
+    pub fn column_get(&self, col: usize) -> Vec<DataValue> {
+        match self.columns.get(col) {
+            Some(column) => column.iter().collect_vec(),
+            None => vec![],
+        }
     }
     pub fn column_get_print(&self, col: usize) -> Vec<String> {
-        self.rows
-            .iter()
-            .filter_map(|row| row.get(col))
-            .map(|e| e.print())
+        (0..self.height)
+            .map(|row| self.get_print(row, col).into_owned())
+            .collect_vec()
+    }
+    /// Distinct non-null values of a column, sorted.
+    pub fn column_distinct(&self, col: usize) -> Vec<String> {
+        self.column_get(col)
+            .into_iter()
+            .filter(|v| *v != DataValue::Null)
+            .map(|v| v.print())
+            .sorted()
+            .dedup()
             .collect_vec()
     }
+    /// Turns low cardinality string columns into [`DataType::Enum`] columns.
+    pub fn infer_enums(&mut self, skip: &[bool]) {
+        for col in 0..self.width() {
+            if skip.get(col).copied().unwrap_or(false)
+                || self.headers[col].dtype() != &DataType::String
+            {
+                continue;
+            }
+            let values = self.column_distinct(col);
+            let filled = self
+                .column_get(col)
+                .into_iter()
+                .filter(|v| *v != DataValue::Null)
+                .count();
+            if !values.is_empty() && values.len() <= ENUM_MAX_VALUES && values.len() * 4 <= filled {
+                self.headers[col].set_dtype(DataType::Enum(values));
+            }
+        }
+    }
     pub fn dtype_column(&self, col: usize) -> Option<DataType> {
         if self.is_valid_col(col) {
             Some(
@@ -102,43 +174,64 @@ impl DataFrame {
         }
     }
     pub fn append_empty_row(&mut self) {
-        self.rows.push(DataRow::new(self.width()));
+        let row = self
+            .headers
+            .iter()
+            .map(Header::default_parsed)
+            .collect_vec();
+        self.push_row(row.into());
     }
     pub fn append_empty_column(&mut self, dtype: DataType) {
-        let header = Header::new("new column").with_dtype(dtype);
+        let header = Header::new(&self.unique_header_name("new column", None)).with_dtype(dtype);
         info!("{:#?}", &header);
         self.headers.push(header);
-        self.rows.iter_mut().for_each(|r| r.push(DataValue::Null));
+        self.columns.push(Column::nulls(self.height));
     }
     pub fn append_empty_column_named(&mut self, dtype: DataType, name: &str) {
-        let header = Header::new(name).with_dtype(dtype);
+        let header = Header::new(&self.unique_header_name(name, None)).with_dtype(dtype);
         self.headers.push(header);
-        self.rows.iter_mut().for_each(|r| r.push(DataValue::Null));
+        self.columns.push(Column::nulls(self.height));
+    }
+    /// Inserts `values` before row `row`, see [`DataFrame::push_row`].
+    pub(super) fn insert_row(&mut self, row: usize, values: DataRow) {
+        self.push_row(values);
+        let last = self.height - 1;
+        let order = (0..self.height)
+            .map(|i| match i.cmp(&row) {
+                std::cmp::Ordering::Less => i,
+                std::cmp::Ordering::Equal => last,
+                std::cmp::Ordering::Greater => i - 1,
+            })
+            .collect_vec();
+        self.columns.iter_mut().for_each(|c| c.permute(&order));
+        self.source.iter_mut().for_each(|s| s.permute(&order));
     }
     pub fn remove_row(&mut self, row: usize) {
         if self.is_valid_row(row) {
-            self.rows.remove(row);
+            self.columns.iter_mut().for_each(|c| c.remove(row));
+            self.source.iter_mut().for_each(|s| s.remove(row));
+            self.height -= 1;
         }
     }
     pub fn remove_column(&mut self, col: usize) {
         if self.is_valid_col(col) {
             self.headers.remove(col);
-            self.rows.iter_mut().for_each(|row| {
-                row.remove(col);
-            });
+            self.columns.remove(col);
         }
     }
     pub fn move_row_up(&mut self, row: usize) -> Option<usize> {
         if self.is_valid_row(row) && row > 0 {
-            self.rows.swap(row, row - 1);
+            self.columns.iter_mut().for_each(|c| c.swap(row, row - 1));
+            self.source.iter_mut().for_each(|s| s.swap(row, row - 1));
             Some(row - 1)
         } else {
             None
         }
     }
     pub fn move_row_down(&mut self, row: usize) -> Option<usize> {
-        if self.is_valid_row(row) && row < self.rows.len() - 1 {
-            self.rows.swap(row, row + 1);
+        if self.is_valid_row(row) && row < self.height - 1 {
+            self.columns.iter_mut().for_each(|c| c.swap(row, row + 1));
+            self.source.iter_mut().for_each(|s| s.swap(row, row + 1));
             Some(row + 1)
         } else {
             None
@@ -147,9 +240,7 @@ impl DataFrame {
     pub fn move_column_left(&mut self, col: usize) -> Option<usize> {
         if self.is_valid_col(col) && col > 0 {
             self.headers.swap(col, col - 1);
-            self.rows.iter_mut().for_each(|row| {
-                row.swap(col, col - 1);
-            });
+            self.columns.swap(col, col - 1);
             return Some(col - 1);
         }
         None
@@ -157,9 +248,7 @@ impl DataFrame {
     pub fn move_column_right(&mut self, col: usize) -> Option<usize> {
         if self.is_valid_col(col) && col < self.width() - 1 {
             self.headers.swap(col, col + 1);
-            self.rows.iter_mut().for_each(|row| {
-                row.swap(col, col + 1);
-            });
+            self.columns.swap(col, col + 1);
             return Some(col + 1);
         }
         None
@@ -167,49 +256,46 @@ impl DataFrame {
     pub fn min_header_widths(&self) -> Vec<u16> {
         self.headers
             .iter()
-            .map(|h| h.name().len() as u16)
+            .map(|h| h.name().width() as u16)
             .collect_vec()
     }
     pub fn min_column_widths(&self) -> Vec<u16> {
-        let mut widths = self.min_header_widths();
-        for row in &self.rows {
-            for (column_index, value) in row.iter().enumerate() {
-                if widths.get(column_index).is_some() {
-                    let width = value.print().len() as u16;
-                    if width > widths[column_index] {
-                        widths[column_index] = width;
-                    }
-                }
-            }
-        }
-        widths
+        self.min_header_widths()
+            .into_iter()
+            .zip(self.columns.iter().zip(self.headers.iter()))
+            .map(|(width, (column, header))| width.max(column.width(header)))
+            .collect_vec()
     }
     pub fn column_sort(&mut self, col: usize) {
         if self.is_valid_col(col) {
-            self.rows.sort_by(|a, b| {
-                a.get(col)
-                    .expect("valid column index")
-                    .cmp(b.get(col).expect("valid column index"))
-            });
+            let column = &self.columns[col];
+            let order = (0..self.height)
+                .sorted_by(|a, b| column.cmp_cells(*a, *b))
+                .collect_vec();
+            self.columns.iter_mut().for_each(|c| c.permute(&order));
+            self.source.iter_mut().for_each(|s| s.permute(&order));
         }
     }
     pub fn column_sort_desc(&mut self, col: usize) {
         if self.is_valid_col(col) {
-            self.rows.sort_by(|a, b| {
-                b.get(col)
-                    .expect("valid column index")
-                    .cmp(a.get(col).expect("valid column index"))
-            });
+            let column = &self.columns[col];
+            let order = (0..self.height)
+                .sorted_by(|a, b| column.cmp_cells(*b, *a))
+                .collect_vec();
+            self.columns.iter_mut().for_each(|c| c.permute(&order));
+            self.source.iter_mut().for_each(|s| s.permute(&order));
         }
     }
     pub fn column_set_dtype(&mut self, col: usize, dtype: DataType) {
         if self.is_valid_col(col) {
-            self.headers[col].set_dtype(dtype.clone());
-            self.rows.iter_mut().for_each(|row| {
-                if let Some(value) = row.get_mut(col) {
+            self.headers[col].change_dtype(dtype.clone());
+            self.columns[col] = self.columns[col]
+                .iter()
+                .map(|mut value| {
                     value.convert_dtype(dtype.clone());
-                }
-            });
+                    value
+                })
+                .collect();
         }
     }
 }
@@ -0,0 +1,383 @@
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+};
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use rust_decimal::Decimal;
+use unicode_width::UnicodeWidthStr;
+
+use super::{DataValue, Float, Header};
+
+/// One bit per cell, set for cells holding a value.
+#[derive(Default, Debug, Clone)]
+struct Bitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Bitmap {
+    fn get(&self, i: usize) -> bool {
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+    fn set(&mut self, i: usize, bit: bool) {
+        match bit {
+            true => self.words[i / 64] |= 1 << (i % 64),
+            false => self.words[i / 64] &= !(1 << (i % 64)),
+        }
+    }
+    fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(64) {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, bit);
+    }
+    fn swap(&mut self, i: usize, j: usize) {
+        let (a, b) = (self.get(i), self.get(j));
+        self.set(i, b);
+        self.set(j, a);
+    }
+    /// Drops bit `i`, shifting the bits after it down by one.
+    fn remove(&mut self, i: usize) {
+        let (word, below) = (i / 64, (1u64 << (i % 64)) - 1);
+        let w = self.words[word];
+        self.words[word] = (w & below) | ((w >> 1) & !below);
+        for j in word + 1..self.words.len() {
+            self.words[j - 1] |= (self.words[j] & 1) << 63;
+            self.words[j] >>= 1;
+        }
+        self.len -= 1;
+        if self.len.is_multiple_of(64) {
+            self.words.pop();
+        }
+    }
+    fn permute(&mut self, order: &[usize]) {
+        *self = order.iter().map(|i| self.get(*i)).collect();
+    }
+    fn any(&self) -> bool {
+        self.words.iter().any(|w| *w != 0)
+    }
+}
+
+impl FromIterator<bool> for Bitmap {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bitmap = Bitmap::default();
+        iter.into_iter().for_each(|bit| bitmap.push(bit));
+        bitmap
+    }
+}
+
+/// Distinct strings of a column, cells store an index into it.
+///
+/// Strings stay in the pool when the cells using them change.
+#[derive(Default, Debug, Clone)]
+struct StringPool {
+    strings: Vec<Arc<str>>,
+    ids: HashMap<Arc<str>, u32>,
+}
+
+impl StringPool {
+    fn intern(&mut self, value: &str) -> u32 {
+        if let Some(id) = self.ids.get(value) {
+            return *id;
+        }
+        let id = self.strings.len() as u32;
+        let value: Arc<str> = Arc::from(value);
+        self.strings.push(value.clone());
+        self.ids.insert(value, id);
+        id
+    }
+    fn get(&self, id: u32) -> &str {
+        &self.strings[id as usize]
+    }
+}
+
+/// Cell storage, typed by the first value of the column.
+///
+/// A column holding values of several types falls back to [`ColumnData::Mixed`].
+#[derive(Debug, Clone)]
+enum ColumnData {
+    Null(Vec<()>),
+    Bool(Vec<bool>),
+    Int(Vec<i128>),
+    Float(Vec<f64>),
+    Decimal(Vec<Decimal>),
+    String(Vec<u32>),
+    Date(Vec<NaiveDate>),
+    DateTime(Vec<NaiveDateTime>),
+    DateTimeTz(Vec<DateTime<FixedOffset>>),
+    Time(Vec<NaiveTime>),
+    Duration(Vec<TimeDelta>),
+    Mixed(Vec<DataValue>),
+}
+
+/// Runs `$body` on the cell vector, whatever its type.
+macro_rules! each {
+    ($data:expr, $v:ident => $body:expr) => {
+        match $data {
+            ColumnData::Null($v) => $body,
+            ColumnData::Bool($v) => $body,
+            ColumnData::Int($v) => $body,
+            ColumnData::Float($v) => $body,
+            ColumnData::Decimal($v) => $body,
+            ColumnData::String($v) => $body,
+            ColumnData::Date($v) => $body,
+            ColumnData::DateTime($v) => $body,
+            ColumnData::DateTimeTz($v) => $body,
+            ColumnData::Time($v) => $body,
+            ColumnData::Duration($v) => $body,
+            ColumnData::Mixed($v) => $body,
+        }
+    };
+}
+
+/// Placeholders for cells without a value.
+fn defaults<T: Default + Clone>(len: usize) -> Vec<T> {
+    vec![T::default(); len]
+}
+
+fn push_default<T: Default>(v: &mut Vec<T>) {
+    v.push(T::default());
+}
+
+fn permuted<T: Clone>(v: &[T], order: &[usize]) -> Vec<T> {
+    order.iter().map(|i| v[*i].clone()).collect()
+}
+
+impl ColumnData {
+    /// Empty storage of `len` cells for values like `value`.
+    fn for_value(value: &DataValue, len: usize) -> Self {
+        match value {
+            DataValue::Null => ColumnData::Null(defaults(len)),
+            DataValue::Bool(_) => ColumnData::Bool(defaults(len)),
+            DataValue::Int(_) => ColumnData::Int(defaults(len)),
+            DataValue::Float(_) => ColumnData::Float(defaults(len)),
+            DataValue::Decimal(_) => ColumnData::Decimal(defaults(len)),
+            DataValue::String(_) => ColumnData::String(defaults(len)),
+            DataValue::Date(_) => ColumnData::Date(defaults(len)),
+            DataValue::DateTime(_) => ColumnData::DateTime(defaults(len)),
+            DataValue::DateTimeTz(_) => ColumnData::DateTimeTz(defaults(len)),
+            DataValue::Time(_) => ColumnData::Time(defaults(len)),
+            DataValue::Duration(_) => ColumnData::Duration(defaults(len)),
+            DataValue::Invalid(_) => ColumnData::Mixed(defaults(len)),
+        }
+    }
+}
+
+/// The cells of one column, typed storage with a validity bitmap.
+#[derive(Debug, Clone)]
+pub struct Column {
+    data: ColumnData,
+    valid: Bitmap,
+    strings: StringPool,
+    /// Display width of the widest cell, kept until a cell changes.
+    width: OnceLock<u16>,
+}
+
+impl Default for Column {
+    fn default() -> Self {
+        Self::nulls(0)
+    }
+}
+
+impl FromIterator<DataValue> for Column {
+    fn from_iter<I: IntoIterator<Item = DataValue>>(iter: I) -> Self {
+        let mut column = Column::default();
+        iter.into_iter().for_each(|value| column.push(value));
+        column
+    }
+}
+
+impl Column {
+    pub fn nulls(len: usize) -> Self {
+        Self {
+            data: ColumnData::Null(defaults(len)),
+            valid: (0..len).map(|_| false).collect(),
+            strings: StringPool::default(),
+            width: OnceLock::new(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.valid.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn get(&self, i: usize) -> Option<DataValue> {
+        if i >= self.len() {
+            return None;
+        }
+        if !self.valid.get(i) {
+            return Some(DataValue::Null);
+        }
+        let value = match &self.data {
+            ColumnData::Null(_) => DataValue::Null,
+            ColumnData::Bool(v) => DataValue::Bool(v[i]),
+            ColumnData::Int(v) => DataValue::Int(v[i]),
+            ColumnData::Float(v) => DataValue::Float(Float::new(v[i])),
+            ColumnData::Decimal(v) => DataValue::Decimal(v[i]),
+            ColumnData::String(v) => DataValue::String(self.strings.get(v[i]).to_owned()),
+            ColumnData::Date(v) => DataValue::Date(v[i]),
+            ColumnData::DateTime(v) => DataValue::DateTime(v[i]),
+            ColumnData::DateTimeTz(v) => DataValue::DateTimeTz(v[i]),
+            ColumnData::Time(v) => DataValue::Time(v[i]),
+            ColumnData::Duration(v) => DataValue::Duration(v[i]),
+            ColumnData::Mixed(v) => v[i].clone(),
+        };
+        Some(value)
+    }
+    /// The text of cell `i` if it holds a string, without copying it.
+    pub fn get_str(&self, i: usize) -> Option<&str> {
+        if i >= self.len() || !self.valid.get(i) {
+            return None;
+        }
+        match &self.data {
+            ColumnData::String(v) => Some(self.strings.get(v[i])),
+            ColumnData::Mixed(v) => match &v[i] {
+                DataValue::String(value) => Some(value),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+    /// Cell `i` as `header` prints it, strings are borrowed from the column.
+    pub fn print(&self, i: usize, header: &Header) -> Option<Cow<'_, str>> {
+        match self.get_str(i) {
+            Some(value) => Some(Cow::Borrowed(value)),
+            None => self.get(i).map(|value| Cow::Owned(header.print(&value))),
+        }
+    }
+    /// Whether cell `i` holds `value`, strings are compared without copying them.
+    pub fn holds(&self, i: usize, value: &DataValue) -> bool {
+        match value {
+            DataValue::String(value) => self.get_str(i) == Some(value),
+            value => self.get(i).as_ref() == Some(value),
+        }
+    }
+    /// Whether cell `i` holds text that did not convert to the column's type.
+    pub fn is_invalid(&self, i: usize) -> bool {
+        match &self.data {
+            ColumnData::Mixed(v) => matches!(v.get(i), Some(DataValue::Invalid(_))),
+            _ => false,
+        }
+    }
+    pub fn iter(&self) -> impl Iterator<Item = DataValue> + '_ {
+        (0..self.len()).filter_map(|i| self.get(i))
+    }
+    /// Display width of the widest cell as `header` prints it.
+    ///
+    /// The width is kept until a cell changes, [`Column::forget_width`]
+    /// drops it when the header prints cells differently.
+    pub fn width(&self, header: &Header) -> u16 {
+        *self.width.get_or_init(|| {
+            (0..self.len())
+                .filter_map(|i| self.print(i, header))
+                .map(|value| value.width())
+                .max()
+                .unwrap_or(0) as u16
+        })
+    }
+    pub fn forget_width(&mut self) {
+        self.width.take();
+    }
+    /// Sets cell `i`, changing the storage if it does not fit `value`.
+    pub fn set(&mut self, i: usize, value: DataValue) {
+        if i >= self.len() {
+            return;
+        }
+        self.width.take();
+        if value == DataValue::Null {
+            self.valid.set(i, false);
+            if let ColumnData::Mixed(v) = &mut self.data {
+                v[i] = DataValue::Null;
+            }
+            return;
+        }
+        if !self.fits(&value) {
+            self.data = match self.valid.any() {
+                false => ColumnData::for_value(&value, self.len()),
+                true => ColumnData::Mixed(self.iter().collect()),
+            };
+        }
+        match (&mut self.data, value) {
+            (ColumnData::Bool(v), DataValue::Bool(value)) => v[i] = value,
+            (ColumnData::Int(v), DataValue::Int(value)) => v[i] = value,
+            (ColumnData::Float(v), DataValue::Float(value)) => v[i] = *value,
+            (ColumnData::Decimal(v), DataValue::Decimal(value)) => v[i] = value,
+            (ColumnData::String(v), DataValue::String(value)) => v[i] = self.strings.intern(&value),
+            (ColumnData::Date(v), DataValue::Date(value)) => v[i] = value,
+            (ColumnData::DateTime(v), DataValue::DateTime(value)) => v[i] = value,
+            (ColumnData::DateTimeTz(v), DataValue::DateTimeTz(value)) => v[i] = value,
+            (ColumnData::Time(v), DataValue::Time(value)) => v[i] = value,
+            (ColumnData::Duration(v), DataValue::Duration(value)) => v[i] = value,
+            (ColumnData::Mixed(v), value) => v[i] = value,
+            _ => unreachable!("storage to fit the value"),
+        }
+        self.valid.set(i, true);
+    }
+    fn fits(&self, value: &DataValue) -> bool {
+        matches!(
+            (&self.data, value),
+            (ColumnData::Bool(_), DataValue::Bool(_))
+                | (ColumnData::Int(_), DataValue::Int(_))
+                | (ColumnData::Float(_), DataValue::Float(_))
+                | (ColumnData::Decimal(_), DataValue::Decimal(_))
+                | (ColumnData::String(_), DataValue::String(_))
+                | (ColumnData::Date(_), DataValue::Date(_))
+                | (ColumnData::DateTime(_), DataValue::DateTime(_))
+                | (ColumnData::DateTimeTz(_), DataValue::DateTimeTz(_))
+                | (ColumnData::Time(_), DataValue::Time(_))
+                | (ColumnData::Duration(_), DataValue::Duration(_))
+                | (ColumnData::Mixed(_), _)
+        )
+    }
+    pub fn push(&mut self, value: DataValue) {
+        each!(&mut self.data, v => push_default(v));
+        self.valid.push(false);
+        self.set(self.len() - 1, value);
+    }
+    pub fn remove(&mut self, i: usize) {
+        if i < self.len() {
+            self.width.take();
+            each!(&mut self.data, v => {
+                v.remove(i);
+            });
+            self.valid.remove(i);
+        }
+    }
+    pub fn swap(&mut self, i: usize, j: usize) {
+        each!(&mut self.data, v => v.swap(i, j));
+        self.valid.swap(i, j);
+    }
+    /// Reorders the cells, cell `i` becomes the former cell `order[i]`.
+    pub fn permute(&mut self, order: &[usize]) {
+        each!(&mut self.data, v => *v = permuted(v, order));
+        self.valid.permute(order);
+    }
+    /// Compares two cells like their [`DataValue`]s, nulls first.
+    pub fn cmp_cells(&self, i: usize, j: usize) -> Ordering {
+        match (self.valid.get(i), self.valid.get(j)) {
+            (false, false) => return Ordering::Equal,
+            (false, true) => return Ordering::Less,
+            (true, false) => return Ordering::Greater,
+            (true, true) => {}
+        }
+        match &self.data {
+            ColumnData::Null(_) => Ordering::Equal,
+            ColumnData::Bool(v) => v[i].cmp(&v[j]),
+            ColumnData::Int(v) => v[i].cmp(&v[j]),
+            ColumnData::Float(v) => Float::new(v[i]).cmp(&Float::new(v[j])),
+            ColumnData::Decimal(v) => v[i].cmp(&v[j]),
+            ColumnData::String(v) => self.strings.get(v[i]).cmp(self.strings.get(v[j])),
+            ColumnData::Date(v) => v[i].cmp(&v[j]),
+            ColumnData::DateTime(v) => v[i].cmp(&v[j]),
+            ColumnData::DateTimeTz(v) => v[i].cmp(&v[j]),
+            ColumnData::Time(v) => v[i].cmp(&v[j]),
+            ColumnData::Duration(v) => v[i].cmp(&v[j]),
+            ColumnData::Mixed(v) => v[i].cmp(&v[j]),
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use std::{
+    collections::HashSet,
+    ops::{Deref, DerefMut},
+};
+
+use chrono::NaiveDate;
+use itertools::Itertools;
+use rust_decimal::Decimal;
+
+use super::{Column, DataFrame, DataRow, DataValue, FrameError, FrameResult, Header};
+
+/// A row of the frame, for access by column name.
+#[derive(Debug, Clone, Copy)]
+pub struct RowView<'a> {
+    df: &'a DataFrame,
+    row: usize,
+}
+
+impl<'a> RowView<'a> {
+    /// The value of the first column called `name`.
+    pub fn get(&self, name: &str) -> Option<DataValue> {
+        self.df
+            .headers
+            .iter()
+            .position(|h| h.name() == name)
+            .and_then(|col| self.df.get(self.row, col))
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, DataValue)> + 'a {
+        let (df, row) = (self.df, self.row);
+        df.headers.iter().map(Header::name).zip(
+            df.columns
+                .iter()
+                .map(move |c| c.get(row).unwrap_or_default()),
+        )
+    }
+    pub fn row(&self) -> DataRow {
+        self.df.row_get(self.row).expect("row to be in range")
+    }
+}
+
+/// A cell to change in place, written back to its column when dropped.
+#[derive(Debug)]
+pub struct CellMut<'a> {
+    column: &'a mut Column,
+    row: usize,
+    value: DataValue,
+}
+
+impl<'a> CellMut<'a> {
+    pub(super) fn new(column: &'a mut Column, row: usize) -> Option<Self> {
+        let value = column.get(row)?;
+        Some(Self { column, row, value })
+    }
+}
+
+impl Deref for CellMut<'_> {
+    type Target = DataValue;
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl DerefMut for CellMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl Drop for CellMut<'_> {
+    fn drop(&mut self) {
+        self.column.set(self.row, std::mem::take(&mut self.value));
+    }
+}
+
+/// The rows of a frame to change in place, those changed are written back
+/// to the columns when dropped.
+///
+/// Rows that are too short are padded with nulls, those too long are cut.
+#[derive(Debug)]
+pub struct RowsMut<'a> {
+    df: &'a mut DataFrame,
+    rows: Vec<DataRow>,
+}
+
+impl<'a> RowsMut<'a> {
+    pub(super) fn new(df: &'a mut DataFrame) -> Self {
+        let rows = df.rows().collect_vec();
+        Self { df, rows }
+    }
+}
+
+impl Deref for RowsMut<'_> {
+    type Target = Vec<DataRow>;
+    fn deref(&self) -> &Self::Target {
+        &self.rows
+    }
+}
+
+impl DerefMut for RowsMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.rows
+    }
+}
+
+impl Drop for RowsMut<'_> {
+    fn drop(&mut self) {
+        let width = self.df.width();
+        let rows = std::mem::take(&mut self.rows)
+            .into_iter()
+            .map(|row| {
+                let mut values = row.into_values();
+                values.resize(width, DataValue::Null);
+                values
+            })
+            .collect_vec();
+        let kept = rows.len().min(self.df.height);
+        let changed = (0..kept)
+            .map(|row| {
+                let columns = self.df.columns.iter();
+                !columns.zip(&rows[row]).all(|(c, v)| c.holds(row, v))
+            })
+            .collect_vec();
+        // a changed row read elsewhere was moved, its source text would stay behind
+        let before: HashSet<Vec<DataValue>> = changed
+            .iter()
+            .positions(|changed| *changed)
+            .filter_map(|row| self.df.row_get(row))
+            .map(DataRow::into_values)
+            .collect();
+        let moved = changed
+            .iter()
+            .positions(|changed| *changed)
+            .any(|row| before.contains(&rows[row]));
+        // the source text only lines up while no rows were added, removed or moved
+        if moved || rows.len() != self.df.height {
+            self.df.source = None;
+        }
+        while self.df.height > rows.len() {
+            self.df.remove_row(self.df.height - 1);
+        }
+        for (row, values) in rows.into_iter().enumerate() {
+            match changed.get(row) {
+                Some(false) => {}
+                Some(true) => self
+                    .df
+                    .columns
+                    .iter_mut()
+                    .zip(values)
+                    .for_each(|(c, v)| c.set(row, v)),
+                None => self.df.push_row(values.into()),
+            }
+        }
+    }
+}
+
+impl DataFrame {
+    /// Index of the column called `name`, an error if there is none or several.
+    pub fn column_index(&self, name: &str) -> FrameResult<usize> {
+        match self
+            .headers
+            .iter()
+            .positions(|h| h.name() == name)
+            .collect_vec()
+            .as_slice()
+        {
+            [col] => Ok(*col),
+            [] => Err(FrameError::ColumnNotFound(name.to_owned())),
+            _ => Err(FrameError::DuplicateColumn(name.to_owned())),
+        }
+    }
+    pub fn column(&self, name: &str) -> FrameResult<Vec<DataValue>> {
+        Ok(self.column_get(self.column_index(name)?))
+    }
+    pub fn header(&self, name: &str) -> FrameResult<&Header> {
+        Ok(&self.headers[self.column_index(name)?])
+    }
+    /// Rows with their values looked up by column name.
+    pub fn rows_named(&self) -> impl Iterator<Item = RowView<'_>> {
+        (0..self.height).map(|row| RowView { df: self, row })
+    }
+
+    /// Values of column `name` converted by `f`, nulls are `None`.
+    fn column_typed<T>(
+        &self,
+        name: &str,
+        f: impl Fn(&DataValue) -> Option<T>,
+    ) -> FrameResult<Vec<Option<T>>> {
+        let col = self.column_index(name)?;
+        self.column_get(col)
+            .into_iter()
+            .map(|value| match value {
+                DataValue::Null => Ok(None),
+                value => match f(&value) {
+                    Some(v) => Ok(Some(v)),
+                    None => Err(FrameError::WrongType {
+                        column: name.to_owned(),
+                        value,
+                    }),
+                },
+            })
+            .collect()
+    }
+    pub fn column_i64(&self, name: &str) -> FrameResult<Vec<Option<i64>>> {
+        self.column_typed(name, |v| match v {
+            DataValue::Int(v) => i64::try_from(*v).ok(),
+            _ => None,
+        })
+    }
+    pub fn column_u64(&self, name: &str) -> FrameResult<Vec<Option<u64>>> {
+        self.column_typed(name, |v| match v {
+            DataValue::Int(v) => u64::try_from(*v).ok(),
+            _ => None,
+        })
+    }
+    /// Floats, integers are widened.
+    pub fn column_f64(&self, name: &str) -> FrameResult<Vec<Option<f64>>> {
+        self.column_typed(name, |v| match v {
+            DataValue::Float(v) => Some(**v),
+            DataValue::Int(v) => Some(*v as f64),
+            _ => None,
+        })
+    }
+    pub fn column_decimal(&self, name: &str) -> FrameResult<Vec<Option<Decimal>>> {
+        self.column_typed(name, |v| match v {
+            DataValue::Decimal(v) => Some(*v),
+            DataValue::Int(v) => Some(Decimal::from_i128_with_scale(*v, 0)),
+            _ => None,
+        })
+    }
+    pub fn column_bool(&self, name: &str) -> FrameResult<Vec<Option<bool>>> {
+        self.column_typed(name, |v| match v {
+            DataValue::Bool(v) => Some(*v),
+            _ => None,
+        })
+    }
+    /// Strings, borrowed from the column.
+    pub fn column_str(&self, name: &str) -> FrameResult<Vec<Option<&str>>> {
+        let column = &self.columns[self.column_index(name)?];
+        (0..self.height)
+            .map(|row| match column.get_str(row) {
+                Some(v) => Ok(Some(v)),
+                None => match column.get(row).unwrap_or_default() {
+                    DataValue::Null => Ok(None),
+                    value => Err(FrameError::WrongType {
+                        column: name.to_owned(),
+                        value,
+                    }),
+                },
+            })
+            .collect()
+    }
+    pub fn column_date(&self, name: &str) -> FrameResult<Vec<Option<NaiveDate>>> {
+        self.column_typed(name, |v| match v {
+            DataValue::Date(v) => Some(*v),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::{DataFrame, DataValue, Header};
+
+/// Validation rules attached to a [`Header`].
+///
+/// Nullability lives on the header itself, see [`Header::nullable`].
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Constraints {
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unique: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "pattern")]
+    pub regex: Option<Regex>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed: Option<Vec<String>>,
+}
+
+impl Constraints {
+    pub fn is_empty(&self) -> bool {
+        !self.unique
+            && self.min.is_none()
+            && self.max.is_none()
+            && self.regex.is_none()
+            && self.allowed.is_none()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ConstraintViolation {
+    Null,
+    Invalid { value: String, dtype: String },
+    NotUnique(String),
+    BelowMin { value: String, min: String },
+    AboveMax { value: String, max: String },
+    NoMatch { value: String, regex: String },
+    NotAllowed { value: String, allowed: Vec<String> },
+}
+
+impl std::error::Error for ConstraintViolation {}
+impl std::fmt::Display for ConstraintViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Null => write!(f, "value is required"),
+            Self::Invalid { value, dtype } => write!(f, "'{value}' is not a valid {dtype}"),
+            Self::NotUnique(value) => write!(f, "'{value}' is not unique"),
+            Self::BelowMin { value, min } => write!(f, "'{value}' is below the minimum {min}"),
+            Self::AboveMax { value, max } => write!(f, "'{value}' is above the maximum {max}"),
+            Self::NoMatch { value, regex } => write!(f, "'{value}' does not match /{regex}/"),
+            Self::NotAllowed { value, allowed } => {
+                write!(f, "'{value}' is not one of {}", allowed.join(", "))
+            }
+        }
+    }
+}
+
+/// A cell that breaks a rule of its column.
+#[derive(Debug, Clone)]
+pub struct Violation {
+    pub row: usize,
+    pub col: usize,
+    pub violation: ConstraintViolation,
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Row {}, Col {}: {}", self.row, self.col, self.violation)
+    }
+}
+
+impl Header {
+    /// Checks everything but uniqueness, which needs the whole column.
+    pub fn check(&self, value: &DataValue) -> Result<(), ConstraintViolation> {
+        if value == &DataValue::Null {
+            return match self.nullable() {
+                true => Ok(()),
+                false => Err(ConstraintViolation::Null),
+            };
+        }
+        if let DataValue::Invalid(value) = value {
+            return Err(ConstraintViolation::Invalid {
+                value: value.to_owned(),
+                dtype: self.dtype().to_string(),
+            });
+        }
+        let constraints = self.constraints();
+        if let Some(min) = constraints.min.as_ref() {
+            if self.parse(min).is_ok_and(|min| value < &min) {
+                return Err(ConstraintViolation::BelowMin {
+                    value: value.print(),
+                    min: min.to_owned(),
+                });
+            }
+        }
+        if let Some(max) = constraints.max.as_ref() {
+            if self.parse(max).is_ok_and(|max| value > &max) {
+                return Err(ConstraintViolation::AboveMax {
+                    value: value.print(),
+                    max: max.to_owned(),
+                });
+            }
+        }
+        if let Some(regex) = constraints.regex.as_ref() {
+            if !regex.is_match(&value.print()) {
+                return Err(ConstraintViolation::NoMatch {
+                    value: value.print(),
+                    regex: regex.to_string(),
+                });
+            }
+        }
+        if let Some(allowed) = constraints.allowed.as_ref() {
+            if !allowed.contains(&value.print()) {
+                return Err(ConstraintViolation::NotAllowed {
+                    value: value.print(),
+                    allowed: allowed.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DataFrame {
+    /// Checks `value` against the rules of column `col`, ignoring the cell at `row`.
+    pub fn check(
+        &self,
+        row: usize,
+        col: usize,
+        value: &DataValue,
+    ) -> Result<(), ConstraintViolation> {
+        let header = &self.headers[col];
+        header.check(value)?;
+        if header.constraints().unique && value != &DataValue::Null {
+            let duplicate = self.columns[col]
+                .iter()
+                .enumerate()
+                .any(|(i, v)| i != row && &v == value);
+            if duplicate {
+                return Err(ConstraintViolation::NotUnique(value.print()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Every cell that breaks a rule of its column.
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (col, header) in self.headers.iter().enumerate() {
+            let values = self.column_get(col);
+            let mut seen: HashMap<&DataValue, usize> = HashMap::new();
+            for (row, value) in values.iter().enumerate() {
+                if let Err(violation) = header.check(value) {
+                    violations.push(Violation {
+                        row,
+                        col,
+                        violation,
+                    });
+                } else if header.constraints().unique && value != &DataValue::Null {
+                    *seen.entry(value).or_default() += 1;
+                }
+            }
+            if header.constraints().unique {
+                for (row, value) in values.iter().enumerate() {
+                    if seen.get(value).is_some_and(|n| *n > 1) {
+                        violations.push(Violation {
+                            row,
+                            col,
+                            violation: ConstraintViolation::NotUnique(value.print()),
+                        });
+                    }
+                }
+            }
+        }
+        violations
+            .into_iter()
+            .sorted_by_key(|v| (v.row, v.col))
+            .collect_vec()
+    }
+}
+
+mod pattern {
+    use regex::Regex;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(regex: &Option<Regex>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match regex {
+            Some(regex) => serializer.serialize_str(regex.as_str()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Regex>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|pattern| Regex::new(&pattern).map_err(D::Error::custom))
+            .transpose()
+    }
+}
@@ -0,0 +1,77 @@
+use itertools::Itertools;
+
+use super::{DataFrame, DataType, DataValue};
+
+/// Shown before a column changes its type, the values that would not convert.
+#[derive(Default, Debug, Clone)]
+pub struct ConversionPreview {
+    pub lost: usize,
+    /// A few of the lost values, distinct.
+    pub examples: Vec<String>,
+}
+
+const PREVIEW_EXAMPLES: usize = 3;
+
+impl DataFrame {
+    /// What converting column `col` to `dtype` would lose.
+    pub fn column_preview_dtype(&self, col: usize, dtype: &DataType) -> ConversionPreview {
+        let lost = self
+            .column_get(col)
+            .into_iter()
+            .filter(|v| v.try_convert_dtype(dtype).is_err())
+            .collect_vec();
+        ConversionPreview {
+            lost: lost.len(),
+            examples: lost
+                .into_iter()
+                .map(|v| v.print())
+                .unique()
+                .take(PREVIEW_EXAMPLES)
+                .collect_vec(),
+        }
+    }
+    /// Infers the type of column `col` anew from its cells as printed, see [`DataType::infer`].
+    pub(super) fn column_reinfer(&mut self, col: usize) {
+        let header = &self.headers[col];
+        let texts = self.columns[col]
+            .iter()
+            .map(|v| header.print(&v))
+            .collect_vec();
+        let dtype = DataType::infer(&texts);
+        if &dtype == header.dtype() {
+            return;
+        }
+        self.columns[col] = texts
+            .iter()
+            .map(|text| dtype.parse(text).unwrap_or_default())
+            .collect();
+        self.headers[col].change_dtype(dtype);
+    }
+    /// Makes column `col` a text column holding its cells as printed.
+    pub(super) fn column_set_text(&mut self, col: usize) {
+        let header = &self.headers[col];
+        self.columns[col] = self.columns[col]
+            .iter()
+            .map(|v| match v {
+                DataValue::Null => DataValue::Null,
+                v => DataValue::String(header.print(&v)),
+            })
+            .collect();
+        self.headers[col].change_dtype(DataType::String);
+    }
+    /// Like [`DataFrame::column_set_dtype`], but keeps values that do not
+    /// convert as [`DataValue::Invalid`] instead of nulling them.
+    pub fn column_set_dtype_keep_invalid(&mut self, col: usize, dtype: DataType) {
+        if self.is_valid_col(col) {
+            self.headers[col].change_dtype(dtype.clone());
+            self.columns[col] = self.columns[col]
+                .iter()
+                .map(|value| {
+                    value
+                        .try_convert_dtype(&dtype)
+                        .unwrap_or_else(|_| DataValue::Invalid(value.print()))
+                })
+                .collect();
+        }
+    }
+}
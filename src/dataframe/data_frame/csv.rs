@@ -1,10 +1,74 @@
-use std::io::Write;
+use std::cell::RefCell;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::ops::ControlFlow;
 use std::path::PathBuf;
+use std::rc::Rc;
 
-use csv::WriterBuilder;
+use csv::{Terminator, WriterBuilder};
 use itertools::Itertools;
 
-use super::{DataFrame, DataRow, DataType, DataValue, FrameError, FrameResult, Header};
+mod dialect;
+mod source;
+pub use dialect::{CsvDialect, CsvDialectHint};
+pub(crate) use source::SourceText;
+use source::{Recorder, Recording, SourceHeader, SourceRow};
+
+use super::{
+    names, ColumnSchema, DataFrame, DataRow, DataType, DataValue, FrameError, FrameResult, Header,
+    Schema,
+};
+
+/// Opt-in reading and inference rules for [`DataFrame::parsed_from_csv`].
+#[derive(Default, Debug, Clone, Copy)]
+pub struct CsvReadOptions {
+    /// Infer numbers written with a decimal point as [`DataType::Decimal`] instead of floats.
+    pub decimals: bool,
+    /// Infer text columns with a handful of distinct values as [`DataType::Enum`].
+    pub enums: bool,
+    /// Fail on empty or repeated header names instead of renaming them.
+    pub strict_headers: bool,
+    /// Lines before the header, like a preamble, that are not read.
+    pub skip_lines: usize,
+    /// Lines starting with this character are not read.
+    pub comment: Option<char>,
+    /// Pad short rows with nulls and cut long rows instead of rejecting them.
+    pub flexible: bool,
+    /// Keep whitespace around fields instead of trimming it.
+    pub keep_whitespace: bool,
+    /// Keep the text of every line read, so that rows left unchanged,
+    /// comments and skipped lines are written back exactly as they were.
+    pub round_trip: bool,
+}
+
+impl CsvReadOptions {
+    /// `text` without the skipped and comment lines, to sniff the dialect from.
+    pub fn data_lines(&self, text: &str) -> String {
+        text.split_inclusive('\n')
+            .skip(self.skip_lines)
+            .filter(|line| !self.comment.is_some_and(|c| line.starts_with(c)))
+            .collect()
+    }
+}
+
+/// Output options for [`DataFrame::to_csv_with`].
+#[derive(Default, Debug, Clone, Copy)]
+pub struct CsvWriteOptions {
+    pub dialect: CsvDialect,
+    /// End lines with `\r\n` instead of `\n`.
+    pub crlf: bool,
+}
+
+/// Rows read between two calls of the progress callback of [`DataFrame::parsed_from_csv_reader`].
+pub const PROGRESS_ROWS: usize = 10_000;
+
+/// How far reading a csv input has come.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct CsvProgress {
+    /// Bytes of the input consumed.
+    pub bytes: u64,
+    /// Lines read, including the ones rejected.
+    pub rows: usize,
+}
 
 #[derive(Default, Debug, Clone)]
 pub struct DataFrameCsvResult {
@@ -13,38 +77,261 @@ pub struct DataFrameCsvResult {
 }
 
 impl DataFrame {
-    pub fn parsed_from_csv(input: &str, delim: char) -> FrameResult<DataFrameCsvResult> {
-        let input: &[u8] = input.as_bytes();
+    /// Parses csv text, column types come from `schema` where it names a column
+    /// and are inferred from the first non-null value otherwise.
+    pub fn parsed_from_csv(
+        input: &str,
+        dialect: CsvDialect,
+        schema: Option<&Schema>,
+        options: CsvReadOptions,
+    ) -> FrameResult<DataFrameCsvResult> {
+        Self::parsed_from_csv_reader(input.as_bytes(), dialect, schema, options, |_, _| {
+            ControlFlow::Continue(())
+        })
+    }
+
+    /// Like [`DataFrame::parsed_from_csv`], but streams the input from `reader`.
+    ///
+    /// Every [`PROGRESS_ROWS`] lines `progress` is called with the frame read so far,
+    /// returning [`ControlFlow::Break`] stops with [`FrameError::Cancelled`].
+    pub fn parsed_from_csv_reader<R: Read>(
+        reader: R,
+        dialect: CsvDialect,
+        schema: Option<&Schema>,
+        options: CsvReadOptions,
+        mut progress: impl FnMut(CsvProgress, &DataFrame) -> ControlFlow<()>,
+    ) -> FrameResult<DataFrameCsvResult> {
+        let recording = options
+            .round_trip
+            .then(|| Rc::new(RefCell::new(Recording::default())));
+        let mut reader = BufReader::new(Recorder::new(reader, recording.clone()));
+        // offset of the csv reader's positions in the input
+        let mut skipped = 0;
+        for _ in 0..options.skip_lines {
+            match reader.skip_until(b'\n')? {
+                0 => break,
+                n => skipped += n,
+            }
+        }
+        let trim = match options.keep_whitespace {
+            true => csv::Trim::None,
+            false => csv::Trim::All,
+        };
         let mut rdr = csv::ReaderBuilder::default()
-            .delimiter(delim as u8)
-            .trim(csv::Trim::All)
-            .has_headers(true)
-            .from_reader(input);
+            .delimiter(dialect.delim as u8)
+            .quote(dialect.quote as u8)
+            .comment(options.comment.map(|c| c as u8))
+            .flexible(options.flexible)
+            .trim(trim)
+            .has_headers(dialect.has_headers)
+            .from_reader(reader);
 
-        let mut rows: Vec<DataRow> = vec![];
         let headers = rdr
             .headers()
-            .map_err(|e| FrameError::Panic(format!("header not valid Utf8. {:}", e)))?;
-
-        let mut headers = headers.iter().map(Header::new).collect_vec();
+            .map_err(|e| FrameError::Panic(format!("header not valid Utf8. {:}", e)))?
+            .clone();
+        // end of the text taken into the source so far
+        let mut taken = 0;
+        let header_text = recording
+            .as_ref()
+            .filter(|_| dialect.has_headers)
+            .map(|rec| {
+                let mut rec = rec.borrow_mut();
+                taken = rec.line_end(skipped + rdr.position().byte() as usize);
+                rec.record_text(0, skipped, taken, options.comment)
+            });
 
+        let mut headers = match dialect.has_headers {
+            true => headers.iter().map(Header::new).collect_vec(),
+            false => (0..headers.len())
+                .map(|i| Header::new(&format!("column{i}")))
+                .collect_vec(),
+        };
         let mut errors: Vec<String> = vec![];
+        if options.strict_headers {
+            if let Some(col) = headers.iter().position(|h| h.name().is_empty()) {
+                return Err(FrameError::InvalidValue(format!(
+                    "column {col} has no name"
+                )));
+            }
+            if let Some(name) = headers.iter().map(Header::name).duplicates().next() {
+                return Err(FrameError::DuplicateColumn(name.to_owned()));
+            }
+        }
+        errors.extend(names::disambiguate_headers(&mut headers));
+        let described: Vec<Option<&ColumnSchema>> = headers
+            .iter()
+            .map(|h| schema.and_then(|s| s.column(h.name())))
+            .collect_vec();
+        headers.iter_mut().zip(described.iter()).for_each(|(h, c)| {
+            if let Some(c) = c {
+                c.apply(h);
+            }
+            if let Some(schema) = schema {
+                let literals = h.format().literals.or(&schema.literals);
+                h.set_format(h.format().clone().with_literals(literals));
+            }
+        });
 
-        for (line_index, res) in rdr.deserialize::<DataRow>().enumerate() {
+        // a column typed Null in the schema is still inferred
+        let columns = described
+            .into_iter()
+            .map(|c| c.filter(|c| c.dtype != DataType::Null))
+            .collect_vec();
+
+        let width = headers.len();
+        let mut df = DataFrame::new(headers, vec![])?;
+        if recording.is_some() {
+            df.source = Some(Box::new(SourceText {
+                header: header_text.map(|(lead, text)| SourceHeader {
+                    lead,
+                    text,
+                    names: df.headers.iter().map(|h| h.name().to_owned()).collect_vec(),
+                }),
+                ..Default::default()
+            }));
+        }
+        let mut record = csv::StringRecord::new();
+        // lines of rows padded or cut to the header width
+        let mut ragged: Vec<usize> = vec![];
+        for line_index in 0usize.. {
+            if line_index > 0 && line_index.is_multiple_of(PROGRESS_ROWS) {
+                let read = CsvProgress {
+                    bytes: rdr.position().byte(),
+                    rows: line_index,
+                };
+                if progress(read, &df).is_break() {
+                    return Err(FrameError::Cancelled);
+                }
+            }
+            let res = match rdr.read_record(&mut record) {
+                Ok(false) => break,
+                Ok(true) => record.deserialize::<DataRow>(None),
+                Err(e) => Err(e),
+            };
+            // the text of the record and of the comment or skipped lines before it
+            let source_text = recording.as_ref().map(|rec| {
+                let mut rec = rec.borrow_mut();
+                let from = rec.line_rest(taken);
+                if from > taken {
+                    df.source.iter_mut().for_each(|s| s.end_last_line());
+                }
+                let end = rec.line_end(skipped + rdr.position().byte() as usize);
+                let text = rec.record_text(from, skipped, end, options.comment);
+                rec.forget(end);
+                taken = end;
+                text
+            });
             match res {
-                Ok(row) => {
-                    headers.iter_mut().zip(row.iter()).for_each(|(h, v)| {
+                Ok(mut row) => {
+                    let line =
+                        record.position().map_or(0, |p| p.line() as usize) + options.skip_lines;
+                    if row.len() != width {
+                        ragged.push(line);
+                        row.resize(width, DataValue::Null);
+                    }
+                    // schema columns are parsed from the raw text, not inferred, text that
+                    // doesn't parse is kept as invalid so the row survives a save
+                    row.iter_mut()
+                        .zip(record.iter())
+                        .zip(df.headers.iter().zip(columns.iter()))
+                        .filter(|(_, (_, c))| c.is_some())
+                        .for_each(|((value, raw), (h, _))| {
+                            *value = h.parse(raw).unwrap_or_else(|e| {
+                                errors.push(format!(
+                                    "Schema error: line: {line} column {}: {e}, kept as invalid",
+                                    h.name()
+                                ));
+                                DataValue::Invalid(raw.to_owned())
+                            });
+                        });
+                    // inferred columns read the configured literals, nulls anywhere and booleans
+                    // in untyped or bool columns, before a number like `1` types the column
+                    row.iter_mut()
+                        .zip(record.iter())
+                        .zip(df.headers.iter().zip(columns.iter()))
+                        .filter(|(_, (_, c))| c.is_none())
+                        .for_each(
+                            |((value, raw), (h, _))| match h.format().literals.parse(raw) {
+                                Some(DataValue::Null) => *value = DataValue::Null,
+                                Some(literal)
+                                    if matches!(h.dtype(), DataType::Null | DataType::Bool) =>
+                                {
+                                    *value = literal
+                                }
+                                _ => {}
+                            },
+                        );
+                    if options.decimals {
+                        row.iter_mut()
+                            .zip(record.iter())
+                            .zip(df.headers.iter().zip(columns.iter()))
+                            .filter(|(_, (_, c))| c.is_none())
+                            .for_each(|((value, raw), (h, _))| {
+                                let numeric = match value {
+                                    DataValue::Float(_) => true,
+                                    DataValue::Int(_) => h.dtype() == &DataType::Decimal,
+                                    _ => false,
+                                };
+                                if numeric {
+                                    if let Ok(decimal) = DataType::Decimal.parse(raw) {
+                                        *value = decimal;
+                                    }
+                                }
+                            });
+                    }
+                    // columns that could still be integers, before this row types them
+                    let integral = df
+                        .headers
+                        .iter()
+                        .map(|h| matches!(h.dtype(), DataType::Int | DataType::Null))
+                        .collect_vec();
+                    df.headers.iter_mut().zip(row.iter()).for_each(|(h, v)| {
                         if h.dtype() == &DataType::Null {
                             h.set_dtype(v.into());
                         }
                     });
-                    if row_matches_header(&row, &headers) {
-                        rows.push(row)
+                    // inferred text columns keep anything as text, like a header line read as data
+                    row.iter_mut()
+                        .zip(record.iter())
+                        .zip(df.headers.iter().zip(columns.iter()))
+                        .filter(|(_, (h, c))| c.is_none() && h.dtype() == &DataType::String)
+                        .for_each(|((value, raw), _)| {
+                            if !matches!(value, DataValue::String(_) | DataValue::Null) {
+                                *value = DataValue::String(raw.to_owned());
+                            }
+                        });
+                    // csv reads integers past 128 bit as floats, rounding them,
+                    // text columns kept them as text above
+                    let overflow = row
+                        .iter()
+                        .zip(record.iter())
+                        .zip(columns.iter().zip(integral.iter()))
+                        .find(|((value, raw), (c, integral))| {
+                            c.is_none()
+                                && **integral
+                                && matches!(value, DataValue::Float(_))
+                                && is_integer(raw)
+                        });
+                    if let Some(((_, raw), _)) = overflow {
+                        return Err(FrameError::InvalidValue(format!(
+                            "line {line}: integer {raw} is out of range, \
+                            declare its column as String in the schema to keep it"
+                        )));
+                    }
+                    if row_matches_header(&row, &df.headers) {
+                        df.push_row(row.clone());
+                        if let (Some(source), Some((lead, text))) = (&mut df.source, source_text) {
+                            *source.rows.last_mut().expect("row to be pushed") = Some(SourceRow {
+                                lead,
+                                text,
+                                values: row,
+                            });
+                        }
                     } else {
                         errors.push(format!(
-                            "Type error: line: {} {row:?} incompatible with header types {:}",
-                            line_index + 2,
-                            headers.iter().map(|h| h.to_debug()).join(",")
+                            "Type error: line: {line} {row:?} incompatible with header types {:}",
+                            df.headers.iter().map(|h| h.to_debug()).join(",")
                         ));
                     }
                 }
@@ -53,20 +340,49 @@ impl DataFrame {
                 }
             }
         }
-        Ok(DataFrameCsvResult {
-            df: DataFrame::new(headers, rows).unwrap(),
-            errors,
-        })
+        if !ragged.is_empty() {
+            errors.push(format!(
+                "Padded or cut {} rows to {width} fields, lines: {}",
+                ragged.len(),
+                ragged.iter().take(10).join(", ")
+            ));
+        }
+        if let (Some(source), Some(rec)) = (&mut df.source, &recording) {
+            let mut rec = rec.borrow_mut();
+            let from = rec.line_rest(taken);
+            if from > taken {
+                source.end_last_line();
+            }
+            source.tail = rec.text(from, rec.end());
+        }
+        if options.enums {
+            df.infer_enums(&columns.iter().map(Option::is_some).collect_vec());
+        }
+        Ok(DataFrameCsvResult { df, errors })
     }
 
     pub fn to_csv(df: &DataFrame) -> FrameResult<String> {
-        let mut wtr = WriterBuilder::new()
-            .delimiter(",".as_bytes()[0])
-            .from_writer(vec![]);
-        wtr.write_record(df.headers.clone().iter().map(|h| h.name()).collect_vec())?;
+        Self::to_csv_with(df, CsvWriteOptions::default())
+    }
 
-        for row in df.rows.clone() {
-            wtr.serialize(row)?;
+    /// `df` as csv, a frame read with [`CsvReadOptions::round_trip`]
+    /// writes the rows it did not change as they were read.
+    pub fn to_csv_with(df: &DataFrame, options: CsvWriteOptions) -> FrameResult<String> {
+        if let Some(source) = &df.source {
+            return source.to_csv(df, options);
+        }
+        let mut wtr = writer(options);
+        if options.dialect.has_headers {
+            wtr.write_record(df.headers.iter().map(|h| h.name()).collect_vec())?;
+        }
+
+        // columns with a format are written as printed, the rest as serialized
+        let formatted = df.headers.iter().any(|h| !h.format().is_default());
+        for row in df.rows() {
+            match formatted {
+                true => wtr.write_record(fields(df, &row))?,
+                false => wtr.serialize(row)?,
+            }
         }
         Ok(String::from_utf8(
             wtr.into_inner()
@@ -74,13 +390,47 @@ impl DataFrame {
         )?)
     }
 }
-fn row_matches_header(row: &[DataValue], headers: &Vec<Header>) -> bool {
-    let r = row
-        .iter()
-        .zip(headers)
-        .map(|(v, h)| &v.dtype() == h.dtype() || v.dtype() == DataType::Null)
-        .all_equal();
-    r
+fn writer(options: CsvWriteOptions) -> csv::Writer<Vec<u8>> {
+    let terminator = match options.crlf {
+        true => Terminator::CRLF,
+        false => Terminator::Any(b'\n'),
+    };
+    WriterBuilder::new()
+        .delimiter(options.dialect.delim as u8)
+        .quote(options.dialect.quote as u8)
+        .terminator(terminator)
+        .from_writer(vec![])
+}
+
+/// The fields `row` is written as, columns with a format as printed.
+fn fields(df: &DataFrame, row: &DataRow) -> Vec<String> {
+    row.iter()
+        .zip(df.headers.iter())
+        .map(|(v, h)| match h.format().is_default() {
+            true => v.to_csv_field(),
+            false => h.print(v),
+        })
+        .collect_vec()
+}
+
+/// A single csv line holding `fields`.
+fn record_text(options: CsvWriteOptions, fields: Vec<String>) -> FrameResult<String> {
+    let mut wtr = writer(options);
+    wtr.write_record(fields)?;
+    Ok(String::from_utf8(
+        wtr.into_inner()
+            .map_err(|e| FrameError::Csv(e.to_string()))?,
+    )?)
+}
+
+/// Whether `text` is an integer, however long.
+fn is_integer(text: &str) -> bool {
+    let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn row_matches_header(row: &[DataValue], headers: &[Header]) -> bool {
+    row.iter().zip(headers).all(|(v, h)| h.dtype().matches(v))
 }
 
 pub fn save_file(path: &PathBuf, content: &[u8]) -> FrameResult<()> {
@@ -0,0 +1,159 @@
+use itertools::Itertools;
+
+/// Delimiters tried by [`CsvDialect::sniff`], earlier ones win ties.
+const DELIMITERS: [char; 4] = [',', ';', '\t', '|'];
+/// Lines of the sample looked at by [`CsvDialect::sniff`].
+const SNIFF_LINES: usize = 20;
+
+/// How the fields of a csv file are separated and quoted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CsvDialect {
+    pub delim: char,
+    pub quote: char,
+    /// The first line holds the column names.
+    pub has_headers: bool,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        Self {
+            delim: ',',
+            quote: '"',
+            has_headers: true,
+        }
+    }
+}
+
+impl std::fmt::Display for CsvDialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let headers = match self.has_headers {
+            true => "header",
+            false => "no header",
+        };
+        write!(f, "{:?} {:?} {}", self.delim, self.quote, headers)
+    }
+}
+
+/// Parts of a [`CsvDialect`] that are known, the rest is sniffed.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct CsvDialectHint {
+    pub delim: Option<char>,
+    pub quote: Option<char>,
+    pub has_headers: Option<bool>,
+}
+
+impl CsvDialect {
+    /// Guesses the dialect from `sample`, the start of a file.
+    ///
+    /// The delimiter giving the most lines with the same number of fields wins,
+    /// a first line that does not look like the lines below it is a header.
+    pub fn sniff(sample: &str, hint: CsvDialectHint) -> Self {
+        let mut lines = sample.lines().take(SNIFF_LINES + 1).collect_vec();
+        // the last line may be cut off
+        if lines.len() > 1 && !sample.ends_with('\n') {
+            lines.pop();
+        }
+        lines.truncate(SNIFF_LINES);
+        let sample = lines.join("\n");
+
+        let delim = hint.delim.unwrap_or_else(|| sniff_delim(&sample));
+        let quote = hint.quote.unwrap_or_else(|| sniff_quote(&sample, delim));
+        let has_headers = hint
+            .has_headers
+            .unwrap_or_else(|| sniff_headers(&records(&sample, delim, quote)));
+        Self {
+            delim,
+            quote,
+            has_headers,
+        }
+    }
+}
+
+fn records(sample: &str, delim: char, quote: char) -> Vec<Vec<String>> {
+    csv::ReaderBuilder::new()
+        .delimiter(delim as u8)
+        .quote(quote as u8)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(sample.as_bytes())
+        .records()
+        .filter_map(Result::ok)
+        .map(|r| r.iter().map(str::to_owned).collect_vec())
+        .collect_vec()
+}
+
+/// The delimiter splitting the most lines into the same number of fields, `,` if none splits.
+fn sniff_delim(sample: &str) -> char {
+    DELIMITERS
+        .iter()
+        .map(|delim| {
+            let widths = records(sample, *delim, '"')
+                .iter()
+                .map(Vec::len)
+                .collect_vec();
+            let (width, count) = widths
+                .iter()
+                .counts()
+                .into_iter()
+                .max_by_key(|(width, count)| (*count, **width))
+                .map(|(width, count)| (*width, count))
+                .unwrap_or((1, 0));
+            (*delim, width, count)
+        })
+        .filter(|(_, width, _)| *width > 1)
+        .rev()
+        .max_by_key(|(_, width, count)| (*count, *width))
+        .map(|(delim, _, _)| delim)
+        .unwrap_or(',')
+}
+
+/// `'` if fields start with it and never with `"`, `"` otherwise.
+fn sniff_quote(sample: &str, delim: char) -> char {
+    let opens = |quote: char| {
+        sample.lines().filter(|l| l.starts_with(quote)).count()
+            + sample.matches(&format!("{delim}{quote}")).count()
+    };
+    match (opens('"'), opens('\'')) {
+        (0, single) if single > 0 => '\'',
+        _ => '"',
+    }
+}
+
+/// Each column votes whether its first value stands out from the values below,
+/// by being text above numbers or by its length. A first value as long as the
+/// values below tells nothing, a code over codes can be a name. Ties keep the header.
+fn sniff_headers(records: &[Vec<String>]) -> bool {
+    let Some((first, rest)) = records.split_first() else {
+        return true;
+    };
+    if rest.is_empty() {
+        return true;
+    }
+    let numeric = |v: &str| v.parse::<f64>().is_ok();
+    let votes: i32 = first
+        .iter()
+        .enumerate()
+        .map(|(col, head)| {
+            let values = rest
+                .iter()
+                .filter_map(|r| r.get(col))
+                .filter(|v| !v.is_empty())
+                .collect_vec();
+            if values.is_empty() {
+                return 0;
+            }
+            if values.iter().all(|v| numeric(v)) {
+                return match numeric(head) {
+                    true => -1,
+                    false => 1,
+                };
+            }
+            match values.iter().map(|v| v.chars().count()).all_equal_value() {
+                Ok(len) if len == head.chars().count() => 0,
+                Ok(_) => 1,
+                Err(_) => 0,
+            }
+        })
+        .sum();
+    votes >= 0
+}
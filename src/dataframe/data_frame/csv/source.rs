@@ -0,0 +1,207 @@
+use std::cell::RefCell;
+use std::io::{self, Read};
+use std::rc::Rc;
+
+use itertools::Itertools;
+
+use super::{fields, record_text, CsvWriteOptions};
+use crate::dataframe::{DataFrame, DataRow, FrameResult};
+
+/// Recorded bytes before the last used offset are dropped once there are this many.
+const FORGET_BYTES: usize = 1 << 20;
+
+/// The bytes read through a [`Recorder`] that the parser may still ask for.
+#[derive(Default, Debug)]
+pub(super) struct Recording {
+    /// Offset in the input of the first byte kept.
+    start: usize,
+    bytes: Vec<u8>,
+    /// The last line end found was a `\r` whose `\n` was not read yet.
+    cut_crlf: bool,
+}
+
+impl Recording {
+    /// Offset in the input after the last byte read.
+    pub(super) fn end(&self) -> usize {
+        self.start + self.bytes.len()
+    }
+    /// The input between the offsets `from` and `to`.
+    pub(super) fn text(&self, from: usize, to: usize) -> Box<str> {
+        String::from_utf8_lossy(&self.bytes[from - self.start..to - self.start]).into()
+    }
+    /// `at`, or past the `\n` of a `\r\n` that `at` splits.
+    pub(super) fn line_end(&mut self, at: usize) -> usize {
+        let i = at - self.start;
+        if i == 0 || self.bytes[i - 1] != b'\r' {
+            return at;
+        }
+        match self.bytes.get(i) {
+            Some(b'\n') => at + 1,
+            Some(_) => at,
+            None => {
+                self.cut_crlf = true;
+                at
+            }
+        }
+    }
+    /// `from`, or past the `\n` left over from a `\r\n` that [`Recording::line_end`] cut.
+    pub(super) fn line_rest(&mut self, from: usize) -> usize {
+        let cut = std::mem::take(&mut self.cut_crlf);
+        match cut && self.bytes.get(from - self.start) == Some(&b'\n') {
+            true => from + 1,
+            false => from,
+        }
+    }
+    /// The input between `from` and `to` holding a record, split into the lines
+    /// before the record, those before `data` and blank or comment lines, and the record.
+    pub(super) fn record_text(
+        &self,
+        from: usize,
+        data: usize,
+        to: usize,
+        comment: Option<char>,
+    ) -> (Box<str>, Box<str>) {
+        let data = data.clamp(from, to);
+        let text = self.text(data, to);
+        let lead = text
+            .split_inclusive('\n')
+            .take_while(|line| {
+                line.trim().is_empty() || comment.is_some_and(|c| line.starts_with(c))
+            })
+            .map(str::len)
+            .sum::<usize>();
+        let (before, record) = text.split_at(lead);
+        let lead = format!("{}{before}", self.text(from, data));
+        (lead.into(), record.into())
+    }
+    /// Lets go of the input before offset `to`.
+    pub(super) fn forget(&mut self, to: usize) {
+        if to - self.start > FORGET_BYTES {
+            self.bytes.drain(..to - self.start);
+            self.start = to;
+        }
+    }
+}
+
+/// Passes reads through, keeping what was read in the shared [`Recording`] if there is one.
+pub(super) struct Recorder<R> {
+    inner: R,
+    recording: Option<Rc<RefCell<Recording>>>,
+}
+
+impl<R> Recorder<R> {
+    pub(super) fn new(inner: R, recording: Option<Rc<RefCell<Recording>>>) -> Self {
+        Self { inner, recording }
+    }
+}
+
+impl<R: Read> Read for Recorder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        if let Some(recording) = &self.recording {
+            recording.borrow_mut().bytes.extend_from_slice(&buf[..read]);
+        }
+        Ok(read)
+    }
+}
+
+/// The header line as read, with the lines skipped before it.
+#[derive(Debug, Clone)]
+pub(super) struct SourceHeader {
+    pub(super) lead: Box<str>,
+    pub(super) text: Box<str>,
+    /// Column names the line was read as.
+    pub(super) names: Vec<String>,
+}
+
+/// A row as read, with the comment or skipped lines before it.
+#[derive(Debug, Clone)]
+pub(super) struct SourceRow {
+    pub(super) lead: Box<str>,
+    pub(super) text: Box<str>,
+    /// Values the text was read as, the text is written back while the row still holds them.
+    pub(super) values: DataRow,
+}
+
+/// The text a frame was read from, kept in step with its rows so that
+/// rows left unchanged are written back exactly as they were read.
+#[derive(Debug, Clone, Default)]
+pub struct SourceText {
+    pub(super) header: Option<SourceHeader>,
+    /// One entry per row of the frame, `None` for rows that were not read.
+    pub(super) rows: Vec<Option<SourceRow>>,
+    /// Text after the last row, like trailing comments.
+    pub(super) tail: Box<str>,
+}
+
+impl SourceText {
+    /// Completes the `\r` ending the last line kept, see [`Recording::line_rest`].
+    pub(super) fn end_last_line(&mut self) {
+        let last = match self.rows.last_mut() {
+            Some(row) => row.as_mut().map(|r| &mut r.text),
+            None => self.header.as_mut().map(|h| &mut h.text),
+        };
+        if let Some(text) = last.filter(|t| t.ends_with('\r')) {
+            *text = format!("{text}\n").into();
+        }
+    }
+    pub(crate) fn push(&mut self) {
+        self.rows.push(None);
+    }
+    pub(crate) fn remove(&mut self, row: usize) {
+        self.rows.remove(row);
+    }
+    pub(crate) fn swap(&mut self, a: usize, b: usize) {
+        self.rows.swap(a, b);
+    }
+    /// Reorders the rows, row `i` becomes the former row `order[i]`.
+    pub(crate) fn permute(&mut self, order: &[usize]) {
+        let mut rows = std::mem::take(&mut self.rows);
+        self.rows = order.iter().map(|i| rows[*i].take()).collect_vec();
+    }
+
+    /// `df` as csv, rows still holding the values they were read as are
+    /// written as read, the others as [`DataFrame::to_csv_with`] writes them.
+    pub(super) fn to_csv(&self, df: &DataFrame, options: CsvWriteOptions) -> FrameResult<String> {
+        let terminator = match options.crlf {
+            true => "\r\n",
+            false => "\n",
+        };
+        let mut out = String::new();
+        if options.dialect.has_headers {
+            let names = df
+                .headers()
+                .iter()
+                .map(|h| h.name().to_owned())
+                .collect_vec();
+            match &self.header {
+                Some(header) if header.names == names => {
+                    out.push_str(&header.lead);
+                    out.push_str(&header.text);
+                }
+                header => {
+                    out.extend(header.iter().map(|h| &*h.lead));
+                    out.push_str(&record_text(options, names)?);
+                }
+            }
+        }
+        for (row, source) in df.rows().zip(self.rows.iter()) {
+            // the last line read may lack a line ending
+            if !out.is_empty() && !out.ends_with('\n') {
+                out.push_str(terminator);
+            }
+            match source {
+                Some(source) if **source.values == *row => {
+                    out.push_str(&source.lead);
+                    out.push_str(&source.text);
+                }
+                source => {
+                    out.extend(source.iter().map(|s| &*s.lead));
+                    out.push_str(&record_text(options, fields(df, &row))?);
+                }
+            }
+        }
+        out.push_str(&self.tail);
+        Ok(out)
+    }
+}
@@ -30,8 +30,17 @@ where
     }
 }
 
+impl From<Vec<DataValue>> for DataRow {
+    fn from(values: Vec<DataValue>) -> Self {
+        Self(values)
+    }
+}
+
 impl DataRow {
     pub fn new(len: usize) -> Self {
         Self(vec![DataValue::Null; len])
     }
+    pub fn into_values(self) -> Vec<DataValue> {
+        self.0
+    }
 }
@@ -1,20 +1,38 @@
-use chrono::NaiveDate;
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
-use super::DataValue;
+use super::{value::temporal, DataValue, Format};
 
 #[derive(Debug, Clone)]
 pub enum DataTypeParseError {
     StringNotParseableAsBool(String),
     ParseIntError(String),
     ParseFloatError(String),
+    ParseDecimalError(String),
     ParseDateError(String),
+    ParseDateTimeError(String),
+    ParseTimeError(String),
+    ParseDurationError(String),
+    NotAnEnumValue(String),
 }
 
 impl std::error::Error for DataTypeParseError {}
 impl std::fmt::Display for DataTypeParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Self::StringNotParseableAsBool(v) => write!(f, "'{}' is neither true nor false", v),
+            Self::ParseIntError(e) => write!(f, "{}", e),
+            Self::ParseFloatError(e) => write!(f, "{}", e),
+            Self::ParseDecimalError(v) => write!(f, "'{}' is not a decimal number", v),
+            Self::ParseDateError(e) => write!(f, "{}", e),
+            Self::ParseDateTimeError(v) => write!(f, "'{}' is not a timestamp", v),
+            Self::ParseTimeError(v) => write!(f, "'{}' is not a time of day", v),
+            Self::ParseDurationError(v) => write!(f, "'{}' is not an ISO 8601 duration", v),
+            Self::NotAnEnumValue(v) => write!(f, "'{}' is not one of the allowed values", v),
+        }
     }
 }
 
@@ -24,13 +42,27 @@ pub enum DataType {
     Bool,
     Int,
     Float,
+    /// An exact fixed-point number, keeps the scale it was written with.
+    Decimal,
     String,
     Date,
+    /// A timestamp, with or without an offset.
+    DateTime,
+    Time,
+    Duration,
+    /// A string restricted to a fixed set of values.
+    Enum(Vec<String>),
 }
 
+/// String columns with at most this many distinct values are inferred as [`DataType::Enum`].
+pub const ENUM_MAX_VALUES: usize = 10;
+
 impl std::fmt::Display for DataType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            DataType::Enum(values) => write!(f, "Enum({})", values.len()),
+            _ => write!(f, "{:?}", self),
+        }
     }
 }
 
@@ -40,40 +72,140 @@ impl From<&DataValue> for DataType {
             DataValue::Null => DataType::Null,
             DataValue::Bool(_) => DataType::Bool,
             DataValue::Float(_) => DataType::Float,
+            DataValue::Decimal(_) => DataType::Decimal,
             DataValue::Int(_) => DataType::Int,
             DataValue::String(_) => DataType::String,
             DataValue::Date(_) => DataType::Date,
+            DataValue::DateTime(_) => DataType::DateTime,
+            DataValue::DateTimeTz(_) => DataType::DateTime,
+            DataValue::Time(_) => DataType::Time,
+            DataValue::Duration(_) => DataType::Duration,
+            DataValue::Invalid(_) => DataType::String,
         }
     }
 }
 
 impl DataType {
+    /// The narrowest type all non-empty `values` parse as, [`DataType::Null`] if all are empty.
+    ///
+    /// Numbers floats would round, like long amounts, are [`DataType::Decimal`].
+    pub fn infer(values: &[impl AsRef<str>]) -> DataType {
+        let values = values
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            return DataType::Null;
+        }
+        [
+            DataType::Bool,
+            DataType::Int,
+            DataType::Float,
+            DataType::Date,
+            DataType::DateTime,
+            DataType::Time,
+            DataType::Duration,
+        ]
+        .into_iter()
+        .find(|dtype| values.iter().all(|v| dtype.parse(v).is_ok()))
+        .map(|dtype| match dtype {
+            DataType::Float
+                if values.iter().any(|v| !fits_float(v))
+                    && values.iter().all(|v| DataType::Decimal.parse(v).is_ok()) =>
+            {
+                DataType::Decimal
+            }
+            dtype => dtype,
+        })
+        .unwrap_or(DataType::String)
+    }
+    /// Whether `value` can be stored in a column of this type.
+    pub fn matches(&self, value: &DataValue) -> bool {
+        match (self, value) {
+            (_, DataValue::Null) | (_, DataValue::Invalid(_)) => true,
+            (DataType::Enum(values), DataValue::String(v)) => values.contains(v),
+            (dtype, value) => dtype == &value.dtype(),
+        }
+    }
     pub fn parse(&self, value: &str) -> Result<DataValue, DataTypeParseError> {
+        self.parse_with(value, &Format::default())
+    }
+    /// Parses `value` written in `format`, see [`DataValue::print_with`].
+    pub fn parse_with(
+        &self,
+        value: &str,
+        format: &Format,
+    ) -> Result<DataValue, DataTypeParseError> {
+        let pattern = format.pattern.as_deref();
+        if !value.is_empty() && format.literals.is_null(value) {
+            return Ok(DataValue::Null);
+        }
         match self {
             DataType::Null => Ok(DataValue::Null),
-            DataType::Bool => match value {
-                "true" => Ok(DataValue::Bool(true)),
-                "false" => Ok(DataValue::Bool(false)),
-                _ => Err(DataTypeParseError::StringNotParseableAsBool(
+            DataType::Bool => match format.literals.parse_bool(value) {
+                Some(v) => Ok(DataValue::Bool(v)),
+                None => Err(DataTypeParseError::StringNotParseableAsBool(
                     value.to_owned(),
                 )),
             },
-            DataType::Int => match value.parse::<i64>() {
+            DataType::Int => match format.normalize_number(value).parse::<i128>() {
                 Ok(v) => Ok(DataValue::Int(v)),
                 Err(e) => Err(DataTypeParseError::ParseIntError(e.to_string())),
             },
-            DataType::Float => match value.parse::<f64>() {
+            DataType::Float => match format.normalize_number(value).parse::<f64>() {
                 Ok(v) => Ok(DataValue::Float(v.into())),
                 Err(e) => Err(DataTypeParseError::ParseFloatError(e.to_string())),
             },
+            DataType::Decimal => match Decimal::from_str(&format.normalize_number(value)) {
+                Ok(v) => Ok(DataValue::Decimal(v)),
+                Err(_) => Err(DataTypeParseError::ParseDecimalError(value.to_owned())),
+            },
             DataType::String => match value.is_empty() {
                 true => Ok(DataValue::Null),
                 false => Ok(DataValue::String(value.to_owned())),
             },
-            DataType::Date => match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            DataType::Date => match NaiveDate::parse_from_str(value, pattern.unwrap_or("%Y-%m-%d"))
+            {
                 Ok(v) => Ok(DataValue::Date(v)),
                 Err(e) => Err(DataTypeParseError::ParseDateError(e.to_string())),
             },
+            DataType::DateTime => match pattern {
+                Some(pattern) => DateTime::parse_from_str(value, pattern)
+                    .ok()
+                    .map(DataValue::DateTimeTz)
+                    .or_else(|| {
+                        NaiveDateTime::parse_from_str(value, pattern)
+                            .ok()
+                            .map(DataValue::DateTime)
+                    }),
+                None => temporal::parse_datetime_tz(value)
+                    .map(DataValue::DateTimeTz)
+                    .or_else(|| temporal::parse_datetime(value).map(DataValue::DateTime)),
+            }
+            .ok_or_else(|| DataTypeParseError::ParseDateTimeError(value.to_owned())),
+            DataType::Time => match pattern {
+                Some(pattern) => NaiveTime::parse_from_str(value, pattern).ok(),
+                None => temporal::parse_time(value),
+            }
+            .map(DataValue::Time)
+            .ok_or_else(|| DataTypeParseError::ParseTimeError(value.to_owned())),
+            DataType::Duration => match temporal::parse_duration(value) {
+                Some(v) => Ok(DataValue::Duration(v)),
+                None => Err(DataTypeParseError::ParseDurationError(value.to_owned())),
+            },
+            DataType::Enum(values) => match values.iter().any(|v| v == value) {
+                true => Ok(DataValue::String(value.to_owned())),
+                false => Err(DataTypeParseError::NotAnEnumValue(value.to_owned())),
+            },
         }
     }
 }
+
+/// Whether `value` reads as a float without losing digits.
+fn fits_float(value: &str) -> bool {
+    match (Decimal::from_str(value), value.parse::<f64>()) {
+        (Ok(exact), Ok(float)) => Decimal::from_str(&float.to_string()).is_ok_and(|f| f == exact),
+        _ => true,
+    }
+}
@@ -1,5 +1,7 @@
 // use serde::{Deserialize, Serialize};
 
+use super::{ConstraintViolation, DataTypeParseError, DataValue};
+
 pub type FrameResult<T> = std::result::Result<T, FrameError>;
 
 #[derive(Debug, Clone)]
@@ -8,13 +10,44 @@ pub enum FrameError {
     Csv(String),
     Utf8(String),
     Io(String),
+    Serde(String),
     NotUniformColumnWidths,
+    InvalidValue(String),
+    Constraint(ConstraintViolation),
+    ColumnNotFound(String),
+    DuplicateColumn(String),
+    WrongType {
+        column: String,
+        value: DataValue,
+    },
+    /// Reading was stopped before the end of the input.
+    Cancelled,
 }
 
 impl std::error::Error for FrameError {}
 impl std::fmt::Display for FrameError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Self::InvalidValue(msg) => write!(f, "{}", msg),
+            Self::Constraint(violation) => write!(f, "{}", violation),
+            Self::ColumnNotFound(name) => write!(f, "no column named '{}'", name),
+            Self::DuplicateColumn(name) => write!(f, "several columns are named '{}'", name),
+            Self::Cancelled => write!(f, "loading was cancelled"),
+            Self::WrongType { column, value } => {
+                write!(
+                    f,
+                    "column '{}' holds {:?}, not the requested type",
+                    column, value
+                )
+            }
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+impl From<ConstraintViolation> for FrameError {
+    fn from(value: ConstraintViolation) -> Self {
+        Self::Constraint(value)
     }
 }
 
@@ -30,6 +63,18 @@ impl From<std::string::FromUtf8Error> for FrameError {
     }
 }
 
+impl From<DataTypeParseError> for FrameError {
+    fn from(value: DataTypeParseError) -> Self {
+        Self::InvalidValue(value.to_string())
+    }
+}
+
+impl From<serde_json::Error> for FrameError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Serde(value.to_string())
+    }
+}
+
 impl From<std::io::Error> for FrameError {
     fn from(value: std::io::Error) -> Self {
         Self::Io(value.to_string())
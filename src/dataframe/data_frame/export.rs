@@ -1,15 +1,16 @@
 use indexmap::IndexMap;
 use itertools::Itertools;
+use serde_json::{json, Map, Value};
 
-use super::{DataFrame, DataValue};
+use super::{DataFrame, DataType, DataValue, Header};
 
 impl DataFrame {
-    pub fn mapped_rows(&self) -> Vec<IndexMap<&str, &DataValue>> {
-        self.rows
-            .iter()
+    pub fn mapped_rows(&self) -> Vec<IndexMap<&str, DataValue>> {
+        self.rows()
             .map(|row| {
                 let mut map = IndexMap::new();
-                row.iter()
+                row.into_values()
+                    .into_iter()
                     .zip(self.headers.iter())
                     .for_each(|(value, header)| {
                         map.insert(header.name(), value);
@@ -20,14 +21,14 @@ impl DataFrame {
     }
 
     pub fn mapped_rows_owned(&self) -> Vec<IndexMap<String, DataValue>> {
-        self.rows
-            .iter()
+        self.rows()
             .map(|row| {
                 let mut map = IndexMap::new();
-                row.iter()
+                row.into_values()
+                    .into_iter()
                     .zip(self.headers.iter())
                     .for_each(|(value, header)| {
-                        map.insert(header.name().to_string(), value.clone());
+                        map.insert(header.name().to_string(), value);
                     });
                 map
             })
@@ -43,4 +44,86 @@ impl DataFrame {
         )
         .unwrap()
     }
+
+    /// JSON Schema describing the exported rows, including allowed enum values.
+    pub fn json_schema(&self) -> Value {
+        let mut properties = Map::new();
+        let mut required = vec![];
+        for header in self.headers.iter() {
+            properties.insert(header.name().to_owned(), header.json_schema());
+            if !header.nullable() {
+                required.push(header.name().to_owned());
+            }
+        }
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            },
+        })
+    }
+}
+
+impl Header {
+    pub fn json_schema(&self) -> Value {
+        let mut schema = Map::new();
+        let json_type = match self.dtype() {
+            DataType::Null => "null",
+            DataType::Bool => "boolean",
+            DataType::Int => "integer",
+            DataType::Float => "number",
+            DataType::String
+            | DataType::Decimal
+            | DataType::Date
+            | DataType::DateTime
+            | DataType::Time
+            | DataType::Duration
+            | DataType::Enum(_) => "string",
+        };
+        match self.nullable() {
+            true => schema.insert("type".into(), json!([json_type, "null"])),
+            false => schema.insert("type".into(), json!(json_type)),
+        };
+        let format = match self.dtype() {
+            DataType::Decimal => Some("decimal"),
+            DataType::Date => Some("date"),
+            DataType::DateTime => Some("date-time"),
+            DataType::Time => Some("time"),
+            DataType::Duration => Some("duration"),
+            _ => None,
+        };
+        if let Some(format) = format {
+            schema.insert("format".into(), json!(format));
+        }
+        let constraints = self.constraints();
+        let allowed = match self.dtype() {
+            DataType::Enum(values) => Some(values),
+            _ => constraints.allowed.as_ref(),
+        };
+        if let Some(allowed) = allowed {
+            let mut values = allowed.iter().map(|v| json!(v)).collect_vec();
+            if self.nullable() {
+                values.push(Value::Null);
+            }
+            schema.insert("enum".into(), Value::Array(values));
+        }
+        if let Some(regex) = constraints.regex.as_ref() {
+            schema.insert("pattern".into(), json!(regex.as_str()));
+        }
+        let bounds = match self.dtype() {
+            DataType::Int | DataType::Float => {
+                vec![("minimum", &constraints.min), ("maximum", &constraints.max)]
+            }
+            _ => vec![],
+        };
+        for (key, bound) in bounds {
+            if let Some(Ok(value)) = bound.as_ref().map(|b| self.parse(b)) {
+                schema.insert(key.into(), json!(value));
+            }
+        }
+        Value::Object(schema)
+    }
 }
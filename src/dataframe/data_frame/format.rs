@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+use super::DataValue;
+
+/// How the values of a column are parsed from and printed to text.
+///
+/// Unset fields fall back to the canonical formats, `%Y-%m-%d` for dates
+/// and `.` as decimal separator without grouping for numbers.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Format {
+    /// strftime pattern for dates, timestamps and times.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimal: Option<char>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thousands: Option<char>,
+    /// Number of decimal places floats are printed with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<usize>,
+    #[serde(flatten)]
+    pub literals: Literals,
+}
+
+/// Strings read as true, false and null, the first of each list is the one written.
+///
+/// Empty lists fall back to `true`, `false` and the empty string.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Literals {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub true_values: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub false_values: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub null_values: Vec<String>,
+}
+
+impl Literals {
+    pub fn is_empty(&self) -> bool {
+        self == &Literals::default()
+    }
+
+    /// Fills the lists left empty here from `other`.
+    pub fn or(&self, other: &Literals) -> Literals {
+        let pick = |a: &Vec<String>, b: &Vec<String>| match a.is_empty() {
+            true => b.clone(),
+            false => a.clone(),
+        };
+        Literals {
+            true_values: pick(&self.true_values, &other.true_values),
+            false_values: pick(&self.false_values, &other.false_values),
+            null_values: pick(&self.null_values, &other.null_values),
+        }
+    }
+
+    pub fn is_null(&self, value: &str) -> bool {
+        value.is_empty() || self.null_values.iter().any(|v| v == value)
+    }
+
+    pub fn parse_bool(&self, value: &str) -> Option<bool> {
+        let matches = |values: &Vec<String>, default: &str| match values.is_empty() {
+            true => value == default,
+            false => values.iter().any(|v| v == value),
+        };
+        match (
+            matches(&self.true_values, "true"),
+            matches(&self.false_values, "false"),
+        ) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        }
+    }
+
+    /// The value `value` stands for, if it is one of the configured literals.
+    pub fn parse(&self, value: &str) -> Option<DataValue> {
+        if !self.null_values.is_empty() && self.is_null(value) {
+            return Some(DataValue::Null);
+        }
+        match self.true_values.is_empty() && self.false_values.is_empty() {
+            true => None,
+            false => self.parse_bool(value).map(DataValue::Bool),
+        }
+    }
+
+    pub fn print_bool(&self, value: bool) -> String {
+        let (values, default) = match value {
+            true => (&self.true_values, "true"),
+            false => (&self.false_values, "false"),
+        };
+        values
+            .first()
+            .map(String::as_str)
+            .unwrap_or(default)
+            .to_owned()
+    }
+
+    pub fn print_null(&self) -> String {
+        self.null_values.first().cloned().unwrap_or_default()
+    }
+}
+
+impl Format {
+    pub fn is_default(&self) -> bool {
+        self == &Format::default()
+    }
+
+    pub fn with_pattern(mut self, pattern: &str) -> Self {
+        self.pattern = Some(pattern.to_owned());
+        self
+    }
+
+    pub fn with_separators(mut self, decimal: char, thousands: Option<char>) -> Self {
+        self.decimal = Some(decimal);
+        self.thousands = thousands;
+        self
+    }
+
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    pub fn with_literals(mut self, literals: Literals) -> Self {
+        self.literals = literals;
+        self
+    }
+
+    /// Rewrites a localized number into the form rust parses, `1.234,5` -> `1234.5`.
+    pub fn normalize_number(&self, value: &str) -> String {
+        let mut res: String = match self.thousands {
+            Some(sep) => value.chars().filter(|c| *c != sep).collect(),
+            None => value.to_owned(),
+        };
+        if let Some(sep) = self.decimal {
+            res = res.replace(sep, ".");
+        }
+        res
+    }
+
+    /// Applies separators to a number printed by rust, `1234.5` -> `1.234,5`.
+    pub fn localize_number(&self, value: &str) -> String {
+        let (sign, value) = match value.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", value),
+        };
+        let (int, fraction) = match value.split_once('.') {
+            Some((int, fraction)) => (int, Some(fraction)),
+            None => (value, None),
+        };
+        let int = match self.thousands {
+            Some(sep) if int.chars().all(|c| c.is_ascii_digit()) => group_digits(int, sep),
+            _ => int.to_owned(),
+        };
+        match fraction {
+            Some(fraction) => format!("{sign}{int}{}{fraction}", self.decimal.unwrap_or('.')),
+            None => format!("{sign}{int}"),
+        }
+    }
+}
+
+fn group_digits(digits: &str, sep: char) -> String {
+    let mut res = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            res.push(sep);
+        }
+        res.push(c);
+    }
+    res
+}
@@ -1,17 +1,34 @@
 use serde::{Deserialize, Serialize};
 
-use super::data_type::DataType;
+use super::{data_type::DataType, Constraints, DataTypeParseError, DataValue, Format};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Header {
     name: String,
     dtype: DataType,
+    #[serde(default = "nullable_default")]
+    nullable: bool,
+    #[serde(default)]
+    default: Option<String>,
+    #[serde(default)]
+    format: Format,
+    #[serde(default)]
+    constraints: Constraints,
 }
+
+fn nullable_default() -> bool {
+    true
+}
+
 impl Header {
     pub fn new(name: &str) -> Self {
         Self {
             name: name.to_owned(),
             dtype: DataType::Null,
+            nullable: true,
+            default: None,
+            format: Format::default(),
+            constraints: Constraints::default(),
         }
     }
     pub fn with_dtype(mut self, dtype: DataType) -> Self {
@@ -24,6 +41,59 @@ impl Header {
     pub fn set_dtype(&mut self, dtype: DataType) {
         self.dtype = dtype;
     }
+    /// Sets a new type, dropping the pattern and number format meant for the old one.
+    pub fn change_dtype(&mut self, dtype: DataType) {
+        if self.dtype != dtype {
+            self.format = Format::default().with_literals(self.format.literals.clone());
+        }
+        self.dtype = dtype;
+    }
+    pub fn nullable(&self) -> bool {
+        self.nullable
+    }
+    pub fn set_nullable(&mut self, nullable: bool) {
+        self.nullable = nullable;
+    }
+    pub fn default_value(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+    pub fn set_default_value(&mut self, default: Option<String>) {
+        self.default = default;
+    }
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+    pub fn format(&self) -> &Format {
+        &self.format
+    }
+    pub fn set_format(&mut self, format: Format) {
+        self.format = format;
+    }
+    pub fn constraints(&self) -> &Constraints {
+        &self.constraints
+    }
+    pub fn set_constraints(&mut self, constraints: Constraints) {
+        self.constraints = constraints;
+    }
+    /// Parses `value` as this column's type, empty strings are null.
+    pub fn parse(&self, value: &str) -> Result<DataValue, DataTypeParseError> {
+        match value.is_empty() {
+            true => Ok(DataValue::Null),
+            false => self.dtype.parse_with(value, &self.format),
+        }
+    }
+    /// Prints `value` with this column's format.
+    pub fn print(&self, value: &DataValue) -> String {
+        value.print_with(&self.format)
+    }
+    /// The parsed default value, null if there is none.
+    pub fn default_parsed(&self) -> DataValue {
+        match self.default {
+            Some(ref default) => self.parse(default).unwrap_or(DataValue::Null),
+            None => DataValue::Null,
+        }
+    }
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -31,7 +101,7 @@ impl Header {
         self.name = name.to_owned();
     }
     pub fn to_debug(&self) -> String {
-        format!("{:?}({:})", &self.dtype, &self.name)
+        format!("{:?}({:})", self.dtype, self.name)
     }
 }
 impl std::fmt::Display for Header {
@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use super::{DataFrame, DataRow, DataType, DataValue, Header};
+
+impl DataFrame {
+    /// Names shared by several columns, exports keyed by name would drop all but one.
+    pub fn duplicate_headers(&self) -> Vec<String> {
+        self.headers
+            .iter()
+            .map(Header::name)
+            .duplicates()
+            .map(str::to_owned)
+            .collect_vec()
+    }
+    /// `name`, or `name_2`, `name_3`, .. if a column other than `col` already uses it.
+    pub fn unique_header_name(&self, name: &str, col: Option<usize>) -> String {
+        let taken = self
+            .headers
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != col)
+            .map(|(_, h)| h.name().to_owned())
+            .collect::<HashSet<_>>();
+        unique_name(name, &taken)
+    }
+    /// Makes row `row` the column names, returns a note per name that had to be changed.
+    ///
+    /// Headers keep their type, format and rules, text columns are re-inferred
+    /// as the row may have been all that made them text.
+    pub fn promote_row_to_headers(&mut self, row: usize) -> Vec<String> {
+        let Some(names) = self.row_get(row) else {
+            return vec![];
+        };
+        self.remove_row(row);
+        names
+            .iter()
+            .zip(self.headers.iter_mut())
+            .for_each(|(name, h)| {
+                let name = h.print(name);
+                h.set_name(&name);
+            });
+        let notes = disambiguate_headers(&mut self.headers);
+        for col in 0..self.width() {
+            if matches!(self.headers[col].dtype(), DataType::String | DataType::Null) {
+                self.column_reinfer(col);
+            }
+        }
+        notes
+    }
+    /// Moves the column names into a new first row and names the columns `column0..N`.
+    ///
+    /// Headers keep their type, format and rules, columns whose name
+    /// is not a value of their type, or reads as null, become text.
+    pub fn demote_headers_to_row(&mut self) {
+        let mut row = DataRow::new(self.width());
+        for (col, value) in row.iter_mut().enumerate() {
+            let name = self.headers[col].name().to_owned();
+            // a name read as null, like any name in an empty column, would be lost
+            *value = match self.headers[col].parse(&name) {
+                Ok(parsed) if parsed != DataValue::Null => parsed,
+                _ => {
+                    self.column_set_text(col);
+                    DataValue::String(name)
+                }
+            };
+        }
+        self.insert_row(0, row);
+        for (col, header) in self.headers.iter_mut().enumerate() {
+            header.set_name(&format!("column{col}"));
+        }
+    }
+    /// Renames empty and repeated header names, returns a note per renamed column.
+    pub fn disambiguate_headers(&mut self) -> Vec<String> {
+        disambiguate_headers(&mut self.headers)
+    }
+}
+
+/// Empty names become `column<index>`, repeats of an earlier name get a `_2`, `_3`, .. suffix.
+pub(super) fn disambiguate_headers(headers: &mut [Header]) -> Vec<String> {
+    let mut taken: HashSet<String> = headers.iter().map(|h| h.name().to_owned()).collect();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut notes = vec![];
+    for (col, header) in headers.iter_mut().enumerate() {
+        let name = header.name().to_owned();
+        if !name.is_empty() && seen.insert(name.clone()) {
+            continue;
+        }
+        let base = match name.is_empty() {
+            true => format!("column{col}"),
+            false => name.clone(),
+        };
+        let unique = unique_name(&base, &taken);
+        notes.push(format!("Renamed column {col} '{name}' to '{unique}'"));
+        header.set_name(&unique);
+        taken.insert(unique.clone());
+        seen.insert(unique);
+    }
+    notes
+}
+
+fn unique_name(name: &str, taken: &HashSet<String>) -> String {
+    if !taken.contains(name) {
+        return name.to_owned();
+    }
+    (2..)
+        .map(|i| format!("{name}_{i}"))
+        .find(|n| !taken.contains(n))
+        .expect("a free suffix")
+}
@@ -0,0 +1,99 @@
+use indexmap::IndexMap;
+use itertools::Itertools;
+use serde::{
+    de::{DeserializeOwned, DeserializeSeed},
+    Serialize,
+};
+use serde_json::Value;
+
+use super::{
+    DataFrame, DataRow, DataType, DataValue, FrameError, FrameResult, Header, LiteralSeed, Literals,
+};
+
+impl DataFrame {
+    /// Builds a frame from serializable records, one column per field.
+    ///
+    /// Columns are ordered as the fields first appear, a column's type
+    /// is that of its first non-null value. Strings stay text, only
+    /// numbers and booleans get their own types.
+    pub fn from_records<T: Serialize>(records: &[T]) -> FrameResult<Self> {
+        Self::from_records_with(records, &Literals::default())
+    }
+
+    /// As [`DataFrame::from_records`], values matching `literals` are read as the
+    /// booleans and nulls they stand for and the columns print them back.
+    pub fn from_records_with<T: Serialize>(
+        records: &[T],
+        literals: &Literals,
+    ) -> FrameResult<Self> {
+        let mut maps: Vec<IndexMap<String, DataValue>> = vec![];
+        for record in records {
+            let Value::Object(object) = serde_json::to_value(record)? else {
+                return Err(FrameError::InvalidValue(String::from(
+                    "records must serialize to structs or maps",
+                )));
+            };
+            let map = object
+                .into_iter()
+                .map(|(name, value)| match value {
+                    // read as is, parsing strings would turn "13:45" into a time
+                    Value::String(text) => {
+                        let value = literals.parse(&text).unwrap_or(DataValue::String(text));
+                        Ok((name, value))
+                    }
+                    value => Ok((name, LiteralSeed(literals).deserialize(value)?)),
+                })
+                .collect::<FrameResult<IndexMap<_, _>>>()?;
+            maps.push(map);
+        }
+
+        let mut headers = maps
+            .iter()
+            .flat_map(|map| map.keys())
+            .unique()
+            .map(|name| {
+                let mut header = Header::new(name);
+                header.set_format(header.format().clone().with_literals(literals.clone()));
+                header
+            })
+            .collect_vec();
+        for header in headers.iter_mut() {
+            let dtype = maps
+                .iter()
+                .filter_map(|map| map.get(header.name()))
+                .map(DataValue::dtype)
+                .fold(DataType::Null, common_dtype);
+            header.set_dtype(dtype);
+        }
+        let mut rows = vec![];
+        for mut map in maps.into_iter() {
+            let mut row = DataRow::new(headers.len());
+            for (value, header) in row.iter_mut().zip(headers.iter()) {
+                if let Some(v) = map.swap_remove(header.name()) {
+                    *value = v.try_convert_dtype(header.dtype())?;
+                }
+            }
+            rows.push(row);
+        }
+        DataFrame::new(headers, rows)
+    }
+
+    /// Deserializes every row into a `T`, fields are matched by column name.
+    pub fn to_records<T: DeserializeOwned>(&self) -> FrameResult<Vec<T>> {
+        self.mapped_rows()
+            .iter()
+            .map(|map| Ok(serde_json::from_value(serde_json::to_value(map)?)?))
+            .collect()
+    }
+}
+
+/// A type both values fit, strings if there is no narrower one.
+fn common_dtype(a: DataType, b: DataType) -> DataType {
+    match (a, b) {
+        (DataType::Null, b) => b,
+        (a, DataType::Null) => a,
+        (a, b) if a == b => a,
+        (DataType::Int, DataType::Float) | (DataType::Float, DataType::Int) => DataType::Float,
+        _ => DataType::String,
+    }
+}
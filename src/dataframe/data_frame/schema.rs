@@ -0,0 +1,76 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::{Constraints, DataFrame, DataType, Format, Header, Literals};
+
+/// Persisted description of a single column.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub dtype: DataType,
+    #[serde(default = "nullable_default")]
+    pub nullable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    #[serde(default, skip_serializing_if = "Format::is_default")]
+    pub format: Format,
+    #[serde(flatten)]
+    pub constraints: Constraints,
+}
+
+fn nullable_default() -> bool {
+    true
+}
+
+impl From<&Header> for ColumnSchema {
+    fn from(header: &Header) -> Self {
+        Self {
+            name: header.name().to_owned(),
+            // an empty column has no type yet, Null would parse any text to null on reload
+            dtype: match header.dtype() {
+                DataType::Null => DataType::String,
+                dtype => dtype.clone(),
+            },
+            nullable: header.nullable(),
+            default: header.default_value().map(str::to_owned),
+            format: header.format().clone(),
+            constraints: header.constraints().clone(),
+        }
+    }
+}
+
+impl ColumnSchema {
+    /// Copies everything but the name onto `header`.
+    pub fn apply(&self, header: &mut Header) {
+        header.set_dtype(self.dtype.clone());
+        header.set_nullable(self.nullable);
+        header.set_default_value(self.default.clone());
+        header.set_format(self.format.clone());
+        header.set_constraints(self.constraints.clone());
+    }
+}
+
+/// Column types and constraints, kept next to the csv file
+/// so they survive a reload instead of being inferred again.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Schema {
+    /// Literals for the whole file, columns can override each list.
+    #[serde(default, skip_serializing_if = "Literals::is_empty")]
+    pub literals: Literals,
+    pub columns: Vec<ColumnSchema>,
+}
+
+impl Schema {
+    pub fn column(&self, name: &str) -> Option<&ColumnSchema> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+}
+
+impl DataFrame {
+    pub fn schema(&self) -> Schema {
+        Schema {
+            literals: Literals::default(),
+            columns: self.headers.iter().map(ColumnSchema::from).collect_vec(),
+        }
+    }
+}
@@ -1,23 +1,50 @@
 use chrono::NaiveDate;
-use serde::{de::Visitor, Deserialize, Deserializer};
+use serde::{
+    de::{DeserializeSeed, Visitor},
+    Deserialize, Deserializer,
+};
 use std::fmt;
 
-use super::{DataValue, Float};
+use super::{temporal, DataValue, Float};
+use crate::dataframe::data_frame::Literals;
 // use crate::value::float::Float;
 
-struct ValueVisitor;
+struct ValueVisitor<'a> {
+    literals: Option<&'a Literals>,
+}
+
+impl ValueVisitor<'_> {
+    fn literal(&self, v: &str) -> Option<DataValue> {
+        self.literals.and_then(|l| l.parse(v))
+    }
+}
 
 impl<'de> Deserialize<'de> for DataValue {
     fn deserialize<D>(deserializer: D) -> Result<DataValue, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_any(ValueVisitor)
+        deserializer.deserialize_any(ValueVisitor { literals: None })
+    }
+}
+
+/// Deserializes a [`DataValue`], reading the given literals as booleans and nulls.
+pub struct LiteralSeed<'a>(pub &'a Literals);
+
+impl<'de> DeserializeSeed<'de> for LiteralSeed<'_> {
+    type Value = DataValue;
+    fn deserialize<D>(self, deserializer: D) -> Result<DataValue, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueVisitor {
+            literals: Some(self.0),
+        })
     }
 }
 
 // impl<'de> Visitor<'de> for ValueVisitor {
-impl Visitor<'_> for ValueVisitor {
+impl Visitor<'_> for ValueVisitor<'_> {
     type Value = DataValue;
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         formatter.write_str("SOMETHING HERE")
@@ -28,21 +55,48 @@ impl Visitor<'_> for ValueVisitor {
         E: serde::de::Error,
     {
         // println!("VISITING BOOL {:#?}", v);
-        Ok(DataValue::Bool(v))
+        Ok(self.literal(&v.to_string()).unwrap_or(DataValue::Bool(v)))
     }
     fn visit_u32<E>(self, v: u32) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
         // println!("VISITING U32 {:#?}", v);
-        Ok(DataValue::Int(v as i64))
+        Ok(self
+            .literal(&v.to_string())
+            .unwrap_or(DataValue::Int(v.into())))
+    }
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(self
+            .literal(&v.to_string())
+            .unwrap_or(DataValue::Int(v.into())))
     }
     fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
         // println!("VISITING U64 {:#?}", v);
-        Ok(DataValue::Int(v as i64))
+        Ok(self
+            .literal(&v.to_string())
+            .unwrap_or(DataValue::Int(v.into())))
+    }
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(DataValue::Int(v))
+    }
+    fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match i128::try_from(v) {
+            Ok(v) => Ok(DataValue::Int(v)),
+            Err(_) => Err(E::custom(format!("integer {v} is out of range"))),
+        }
     }
     fn visit_f32<E>(self, v: f32) -> Result<Self::Value, E>
     where
@@ -79,11 +133,22 @@ impl Visitor<'_> for ValueVisitor {
         // println!("VISITING &str {:#?}", v);
         if v.is_empty() {
             Ok(DataValue::Null)
+        } else if let Some(value) = self.literal(v) {
+            Ok(value)
         } else {
             match NaiveDate::parse_from_str(v, "%Y-%m-%d") {
                 Ok(date) => Ok(DataValue::Date(date)),
-                Err(_) => Ok(DataValue::String(v.to_owned())),
+                Err(_) => Ok(parse_temporal(v).unwrap_or(DataValue::String(v.to_owned()))),
             }
         }
     }
 }
+
+/// Infers timestamps, times of day and durations.
+fn parse_temporal(v: &str) -> Option<DataValue> {
+    temporal::parse_datetime_tz(v)
+        .map(DataValue::DateTimeTz)
+        .or_else(|| temporal::parse_datetime(v).map(DataValue::DateTime))
+        .or_else(|| temporal::parse_time(v).map(DataValue::Time))
+        .or_else(|| temporal::parse_duration(v).map(DataValue::Duration))
+}
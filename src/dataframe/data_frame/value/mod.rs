@@ -1,19 +1,32 @@
 mod de;
 mod float;
 mod ser;
-use chrono::NaiveDate;
+pub mod temporal;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+pub use de::LiteralSeed;
 pub use float::Float;
+use rust_decimal::Decimal;
+use std::fmt::Write;
 
-use super::DataType;
+use super::{DataType, DataTypeParseError, Format};
 
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum DataValue {
+    #[default]
     Null,
     String(String),
     Float(Float),
-    Int(i64),
+    Decimal(Decimal),
+    /// Wide enough for every `i64` and `u64`, and 128-bit ids.
+    Int(i128),
     Bool(bool),
     Date(NaiveDate),
+    DateTime(NaiveDateTime),
+    DateTimeTz(DateTime<FixedOffset>),
+    Time(NaiveTime),
+    Duration(TimeDelta),
+    /// Raw text that did not convert to its column's type.
+    Invalid(String),
 }
 impl DataValue {
     pub fn new(v: impl Into<DataValue>) -> Self {
@@ -27,22 +40,72 @@ impl DataValue {
             DataValue::Null => String::new(),
             DataValue::String(v) => v.to_owned(),
             DataValue::Float(float) => float.to_string(),
+            DataValue::Decimal(v) => v.to_string(),
             DataValue::Int(v) => v.to_string(),
             DataValue::Bool(v) => v.to_string(),
             DataValue::Date(v) => v.to_string(),
+            DataValue::DateTime(v) => temporal::format_datetime(v),
+            DataValue::DateTimeTz(v) => temporal::format_datetime_tz(v),
+            DataValue::Time(v) => v.to_string(),
+            DataValue::Duration(v) => temporal::format_duration(v),
+            DataValue::Invalid(v) => v.to_owned(),
         }
     }
-    pub fn convert_dtype(&mut self, dtype: DataType) {
-        match dtype {
-            DataType::Null => *self = DataValue::Null,
-            DataType::String => *self = DataValue::String(self.print()),
-            DataType::Float => *self = dtype.parse(&self.print()).unwrap_or(DataValue::Null),
-            DataType::Int => *self = dtype.parse(&self.print()).unwrap_or(DataValue::Null),
-            DataType::Bool => *self = dtype.parse(&self.print()).unwrap_or(DataValue::Null),
-            DataType::Date => match NaiveDate::parse_from_str(&self.print(), "%Y-%m-%d") {
-                Ok(v) => *self = DataValue::Date(v),
-                Err(_) => *self = DataValue::Null,
+    /// Prints the value in `format`, see [`DataType::parse_with`].
+    pub fn print_with(&self, format: &Format) -> String {
+        let pattern = format.pattern.as_deref();
+        match (self, pattern) {
+            (DataValue::Null, _) => format.literals.print_null(),
+            (DataValue::Bool(v), _) => format.literals.print_bool(*v),
+            (DataValue::Float(float), _) => match format.precision {
+                Some(precision) => format.localize_number(&format!("{:.*}", precision, **float)),
+                None => format.localize_number(&float.to_string()),
+            },
+            (DataValue::Decimal(v), _) => match format.precision {
+                Some(precision) => format.localize_number(&format!("{:.*}", precision, v)),
+                None => format.localize_number(&v.to_string()),
             },
+            (DataValue::Int(v), _) => format.localize_number(&v.to_string()),
+            (DataValue::Date(v), Some(pattern)) => self.print_pattern(v.format(pattern)),
+            (DataValue::DateTime(v), Some(pattern)) => self.print_pattern(v.format(pattern)),
+            (DataValue::DateTimeTz(v), Some(pattern)) => self.print_pattern(v.format(pattern)),
+            (DataValue::Time(v), Some(pattern)) => self.print_pattern(v.format(pattern)),
+            _ => self.print(),
+        }
+    }
+    /// `formatted`, or the value as [`DataValue::print`] prints it when the
+    /// pattern doesn't fit the value, like `%H` for a date.
+    fn print_pattern(&self, formatted: impl std::fmt::Display) -> String {
+        let mut res = String::new();
+        match write!(res, "{formatted}") {
+            Ok(()) => res,
+            Err(_) => self.print(),
+        }
+    }
+    /// The text [`DataValue::serialize`] writes into a csv file.
+    pub fn to_csv_field(&self) -> String {
+        match self {
+            DataValue::Float(float) => format!("{:?}", **float),
+            _ => self.print(),
+        }
+    }
+    /// Converts to `dtype`, values that do not fit become null.
+    pub fn convert_dtype(&mut self, dtype: DataType) {
+        *self = self.try_convert_dtype(&dtype).unwrap_or(DataValue::Null);
+    }
+    /// The value as `dtype`, fails where [`DataValue::convert_dtype`] would lose it.
+    pub fn try_convert_dtype(&self, dtype: &DataType) -> Result<DataValue, DataTypeParseError> {
+        match (self, dtype) {
+            (DataValue::DateTime(v), DataType::Date) => Ok(DataValue::Date(v.date())),
+            (DataValue::DateTimeTz(v), DataType::Date) => Ok(DataValue::Date(v.date_naive())),
+            (DataValue::DateTime(v), DataType::Time) => Ok(DataValue::Time(v.time())),
+            (DataValue::DateTimeTz(v), DataType::Time) => Ok(DataValue::Time(v.time())),
+            (DataValue::Date(v), DataType::DateTime) => {
+                Ok(DataValue::DateTime(v.and_time(NaiveTime::MIN)))
+            }
+            (_, DataType::Null) | (DataValue::Null, _) => Ok(DataValue::Null),
+            (_, DataType::String) => Ok(self.print().into()),
+            (_, dtype) => dtype.parse(&self.print()),
         }
     }
 }
@@ -103,16 +166,61 @@ impl From<f64> for DataValue {
         Self::Float(value.into())
     }
 }
+impl From<Decimal> for DataValue {
+    fn from(value: Decimal) -> Self {
+        Self::Decimal(value)
+    }
+}
 impl From<i32> for DataValue {
     fn from(value: i32) -> Self {
-        Self::Int(value as i64)
+        Self::Int(value.into())
     }
 }
 impl From<i64> for DataValue {
     fn from(value: i64) -> Self {
+        Self::Int(value.into())
+    }
+}
+impl From<u32> for DataValue {
+    fn from(value: u32) -> Self {
+        Self::Int(value.into())
+    }
+}
+impl From<u64> for DataValue {
+    fn from(value: u64) -> Self {
+        Self::Int(value.into())
+    }
+}
+impl From<i128> for DataValue {
+    fn from(value: i128) -> Self {
         Self::Int(value)
     }
 }
+impl From<NaiveDate> for DataValue {
+    fn from(value: NaiveDate) -> Self {
+        Self::Date(value)
+    }
+}
+impl From<NaiveDateTime> for DataValue {
+    fn from(value: NaiveDateTime) -> Self {
+        Self::DateTime(value)
+    }
+}
+impl From<DateTime<FixedOffset>> for DataValue {
+    fn from(value: DateTime<FixedOffset>) -> Self {
+        Self::DateTimeTz(value)
+    }
+}
+impl From<NaiveTime> for DataValue {
+    fn from(value: NaiveTime) -> Self {
+        Self::Time(value)
+    }
+}
+impl From<TimeDelta> for DataValue {
+    fn from(value: TimeDelta) -> Self {
+        Self::Duration(value)
+    }
+}
 impl From<bool> for DataValue {
     fn from(value: bool) -> Self {
         Self::Bool(value)
@@ -1,6 +1,6 @@
 use serde::{Serialize, Serializer};
 
-use super::DataValue;
+use super::{temporal, DataValue};
 
 impl Serialize for DataValue {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -9,11 +9,29 @@ impl Serialize for DataValue {
     {
         match *self {
             DataValue::Null => serializer.serialize_none(),
-            DataValue::Int(value) => serializer.serialize_i64(value),
+            // the narrowest integer that fits, not every format supports 128 bit
+            DataValue::Int(value) => match (i64::try_from(value), u64::try_from(value)) {
+                (Ok(value), _) => serializer.serialize_i64(value),
+                (_, Ok(value)) => serializer.serialize_u64(value),
+                _ => serializer.serialize_i128(value),
+            },
             DataValue::Float(value) => serializer.serialize_f64(*value),
+            // a string, so no format rounds it through a float
+            DataValue::Decimal(value) => serializer.serialize_str(&value.to_string()),
             DataValue::String(ref value) => serializer.serialize_str(value),
             DataValue::Bool(value) => serializer.serialize_bool(value),
             DataValue::Date(value) => serializer.serialize_str(&value.to_string()),
+            DataValue::DateTime(ref value) => {
+                serializer.serialize_str(&temporal::format_datetime(value))
+            }
+            DataValue::DateTimeTz(ref value) => {
+                serializer.serialize_str(&temporal::format_datetime_tz(value))
+            }
+            DataValue::Time(value) => serializer.serialize_str(&value.to_string()),
+            DataValue::Duration(ref value) => {
+                serializer.serialize_str(&temporal::format_duration(value))
+            }
+            DataValue::Invalid(ref value) => serializer.serialize_str(value),
         }
     }
 }
@@ -0,0 +1,112 @@
+use chrono::{DateTime, FixedOffset, NaiveDateTime, NaiveTime, SecondsFormat, TimeDelta};
+
+const DATETIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+];
+const DATETIME_TZ_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S%.f%:z", "%Y-%m-%d %H:%M:%S%.f%#z"];
+const TIME_FORMATS: [&str; 2] = ["%H:%M:%S%.f", "%H:%M"];
+
+/// Parses RFC 3339 and similar timestamps that carry an offset.
+pub fn parse_datetime_tz(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value).ok().or_else(|| {
+        DATETIME_TZ_FORMATS
+            .iter()
+            .find_map(|f| DateTime::parse_from_str(value, f).ok())
+    })
+}
+
+pub fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(value, f).ok())
+}
+
+pub fn parse_time(value: &str) -> Option<NaiveTime> {
+    TIME_FORMATS
+        .iter()
+        .find_map(|f| NaiveTime::parse_from_str(value, f).ok())
+}
+
+pub fn format_datetime(value: &NaiveDateTime) -> String {
+    value.format("%Y-%m-%dT%H:%M:%S%.f").to_string()
+}
+
+pub fn format_datetime_tz(value: &DateTime<FixedOffset>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// Formats a duration as ISO 8601, e.g. `P1DT2H30M` or `-PT0.5S`.
+pub fn format_duration(duration: &TimeDelta) -> String {
+    let sign = if *duration < TimeDelta::zero() {
+        "-"
+    } else {
+        ""
+    };
+    let duration = duration.abs();
+    let days = duration.num_days();
+    let hours = duration.num_hours() % 24;
+    let minutes = duration.num_minutes() % 60;
+    let seconds = duration.num_seconds() % 60;
+    let nanos = duration.subsec_nanos();
+
+    let mut res = format!("{sign}P");
+    if days > 0 {
+        res.push_str(&format!("{days}D"));
+    }
+    if hours > 0 || minutes > 0 || seconds > 0 || nanos > 0 || days == 0 {
+        res.push('T');
+        if hours > 0 {
+            res.push_str(&format!("{hours}H"));
+        }
+        if minutes > 0 {
+            res.push_str(&format!("{minutes}M"));
+        }
+        if nanos > 0 {
+            let fraction = format!("{:09}", nanos);
+            res.push_str(&format!("{seconds}.{}S", fraction.trim_end_matches('0')));
+        } else if seconds > 0 || (hours == 0 && minutes == 0) {
+            res.push_str(&format!("{seconds}S"));
+        }
+    }
+    res
+}
+
+/// Parses an ISO 8601 duration made of weeks, days, hours, minutes and seconds.
+pub fn parse_duration(value: &str) -> Option<TimeDelta> {
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+    let value = value.strip_prefix('P')?;
+    let mut total = TimeDelta::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    let mut components = 0;
+    for c in value.chars() {
+        match c {
+            '0'..='9' | '.' => number.push(c),
+            'T' if !in_time && number.is_empty() => in_time = true,
+            unit => {
+                let n: f64 = number.parse().ok()?;
+                number.clear();
+                let seconds = match (in_time, unit) {
+                    (false, 'W') => n * 604_800.0,
+                    (false, 'D') => n * 86_400.0,
+                    (true, 'H') => n * 3_600.0,
+                    (true, 'M') => n * 60.0,
+                    (true, 'S') => n,
+                    _ => return None,
+                };
+                total += TimeDelta::nanoseconds((seconds * 1e9).round() as i64);
+                components += 1;
+            }
+        }
+    }
+    if !number.is_empty() || components == 0 {
+        return None;
+    }
+    Some(if negative { -total } else { total })
+}
@@ -0,0 +1,20 @@
+//! Typed csv loading, editing and export, the data layer of the `scsv` editor.
+//!
+//! The terminal editor is behind the default `tui` feature, build with
+//! `default-features = false` to use only the [`dataframe`] module.
+
+pub mod dataframe;
+
+#[cfg(feature = "tui")]
+pub mod app;
+#[cfg(feature = "tui")]
+pub mod backend;
+#[cfg(feature = "tui")]
+mod error;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "tui")]
+pub mod utils;
+
+#[cfg(feature = "tui")]
+pub use error::{AppError, AppResult};
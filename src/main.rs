@@ -1,18 +1,15 @@
-use backend::{event_handler::EventHandler, tasks::events::IoCommand};
 use clap::Parser;
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io;
+use std::io::{self, Write};
 use tracing::info;
 
-use crate::{app::App, tui::Tui};
-pub use error::{AppError, AppResult};
-
-pub mod app;
-pub mod backend;
-pub mod dataframe;
-mod error;
-pub mod tui;
-pub mod utils;
+use simple_csv::{
+    app::App,
+    backend::{event_handler::EventHandler, tasks::events::IoCommand},
+    dataframe::csv::{CsvDialectHint, CsvReadOptions},
+    tui::{self, Tui},
+    utils, AppResult,
+};
 
 #[tokio::main]
 async fn main() -> AppResult<()> {
@@ -23,20 +20,36 @@ async fn main() -> AppResult<()> {
 
     let events = EventHandler::new(250);
 
-    if let Some(ref path) = cli.path {
+    if cli.path.is_some() {
         events
             .io_command_sender()
             .send(IoCommand::LoadCsv {
-                path: path.path().to_owned(),
-                delim: cli.delim,
+                path: cli.file_path(),
+                dialect: CsvDialectHint {
+                    delim: cli.delim,
+                    quote: cli.quote,
+                    has_headers: cli.has_headers(),
+                },
+                encoding: cli.encoding,
+                options: CsvReadOptions {
+                    decimals: cli.decimals,
+                    enums: cli.enums,
+                    strict_headers: cli.strict_headers,
+                    skip_lines: cli.skip_lines,
+                    comment: cli.comment,
+                    flexible: cli.flexible,
+                    keep_whitespace: cli.keep_whitespace,
+                    round_trip: cli.round_trip,
+                },
             })
             .unwrap();
     }
 
+    let write_stdout = cli.stdout;
     let mut app = App::new(events.io_command_sender(), cli);
 
     // Initialize the terminal user interface.
-    let backend = CrosstermBackend::new(io::stdout());
+    let backend = CrosstermBackend::new(tui::output()?);
     let terminal = Terminal::new(backend)?;
     let mut tui = Tui::new(terminal, events);
     tui.init()?;
@@ -52,5 +65,12 @@ async fn main() -> AppResult<()> {
 
     // Exit the user interface.
     tui.exit()?;
+
+    // Pass the edited table on, like a filter in a pipeline.
+    if write_stdout {
+        if let Some(bytes) = app.stdout_csv()? {
+            io::stdout().write_all(&bytes)?;
+        }
+    }
     Ok(())
 }
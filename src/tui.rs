@@ -7,9 +7,18 @@ use crossterm::event::{
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use ratatui::backend::Backend;
 use ratatui::Terminal;
-use std::io;
+use std::fs::OpenOptions;
+use std::io::{self, IsTerminal, Write};
 use std::panic;
 
+/// Where the interface is drawn, the terminal even when stdout is piped.
+pub fn output() -> io::Result<Box<dyn Write + Send>> {
+    match io::stdout().is_terminal() {
+        true => Ok(Box::new(io::stdout())),
+        false => Ok(Box::new(OpenOptions::new().write(true).open("/dev/tty")?)),
+    }
+}
+
 /// Representation of a terminal user interface.
 ///
 /// It is responsible for setting up the terminal,
@@ -33,13 +42,13 @@ impl<B: Backend> Tui<B> {
     /// It enables the raw mode and sets terminal properties.
     pub fn init(&mut self) -> AppResult<()> {
         terminal::enable_raw_mode()?;
-        crossterm::execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        crossterm::execute!(output()?, EnterAlternateScreen, EnableMouseCapture)?;
         crossterm::execute!(
-            io::stdout(),
+            output()?,
             PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
         )?;
         crossterm::execute!(
-            io::stdout(),
+            output()?,
             PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
         )?;
         // Define a custom panic hook to reset the terminal properties.
@@ -71,7 +80,7 @@ impl<B: Backend> Tui<B> {
     /// the terminal properties if unexpected errors occur.
     fn reset() -> AppResult<()> {
         terminal::disable_raw_mode()?;
-        crossterm::execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+        crossterm::execute!(output()?, LeaveAlternateScreen, DisableMouseCapture)?;
         Ok(())
     }
 
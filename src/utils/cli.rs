@@ -1,10 +1,75 @@
+use std::path::PathBuf;
+
 use clap::Parser;
 use clio::*;
+use encoding_rs::Encoding;
+
+use crate::backend::file_formats::file_encoding::parse_encoding;
 // use std::io::Write;
 
 #[derive(Parser, Debug, Clone)]
 pub struct Cli {
+    /// Csv file to open, `-` reads it from stdin.
     pub path: Option<ClioPath>,
-    #[clap(value_parser, default_value = ",")]
-    pub delim: char,
+    /// Field delimiter, detected when omitted.
+    #[clap(value_parser)]
+    pub delim: Option<char>,
+    /// Quote character, detected when omitted.
+    #[clap(long)]
+    pub quote: Option<char>,
+    /// The first line is data, columns are named `column0..N`.
+    #[clap(long, conflicts_with = "header")]
+    pub no_header: bool,
+    /// The first line holds the column names, instead of detecting whether it does.
+    #[clap(long)]
+    pub header: bool,
+    /// Read numbers with a decimal point as exact decimals instead of floats.
+    #[clap(long)]
+    pub decimals: bool,
+    /// Read text columns with a handful of distinct values as enums, edited with a picker.
+    #[clap(long)]
+    pub enums: bool,
+    /// Refuse files with empty or repeated column names instead of renaming them.
+    #[clap(long)]
+    pub strict_headers: bool,
+    /// Number of lines before the header to skip, like a preamble.
+    #[clap(long, default_value_t = 0)]
+    pub skip_lines: usize,
+    /// Skip lines starting with this character.
+    #[clap(long)]
+    pub comment: Option<char>,
+    /// Pad short rows with empty cells and cut long rows instead of rejecting them.
+    #[clap(long)]
+    pub flexible: bool,
+    /// Keep whitespace around fields instead of trimming it.
+    #[clap(long)]
+    pub keep_whitespace: bool,
+    /// Write rows that were not changed back exactly as they were read,
+    /// keeping their whitespace, quoting and line endings.
+    #[clap(long)]
+    pub round_trip: bool,
+    /// Read the file in this encoding, like `latin1` or `windows-1252`, instead of detecting it.
+    #[clap(long, value_parser = parse_encoding)]
+    pub encoding: Option<&'static Encoding>,
+    /// Write the table as csv to stdout on quit, to edit the data passing through a pipe.
+    #[clap(long)]
+    pub stdout: bool,
+}
+
+impl Cli {
+    /// Whether the first line holds the column names, `None` to detect it.
+    pub fn has_headers(&self) -> Option<bool> {
+        match (self.header, self.no_header) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        }
+    }
+    /// The file to open, `None` if there is none or the csv comes from stdin.
+    pub fn file_path(&self) -> Option<PathBuf> {
+        self.path
+            .as_ref()
+            .filter(|p| !p.is_std())
+            .map(|p| p.path().to_owned())
+    }
 }